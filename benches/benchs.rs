@@ -1,12 +1,11 @@
 #![feature(test)]
-#![feature(bench_black_box)]
 #![cfg(not(tarpaulin_include))]
 
 extern crate rand;
 extern crate rand_pcg;
 extern crate test;
 
-use evalexpr::build_operator_tree;
+use evalexpr::{build_operator_tree, context_map};
 use rand::{distributions::Uniform, seq::SliceRandom, Rng, SeedableRng};
 use rand_pcg::Pcg32;
 use std::{fmt::Write, hint::black_box};
@@ -17,8 +16,8 @@ const EXPONENTIAL_TUPLE_ITERATIONS: usize = 12;
 
 fn generate_expression<Gen: Rng>(len: usize, gen: &mut Gen) -> String {
     let int_distribution = Uniform::new_inclusive(1, 100);
-    let whitespaces = vec![" ", "", "", "  ", " \n", "       "];
-    let operators = vec!["+", "-", "*", "/", "%", "^"];
+    let whitespaces = [" ", "", "", "  ", " \n", "       "];
+    let operators = ["+", "-", "*", "/", "%", "^"];
     let mut result = String::new();
     write!(result, "{}", gen.sample(int_distribution)).unwrap();
 
@@ -137,3 +136,17 @@ fn bench_evaluate_large_tuple_expression(bencher: &mut Bencher) {
 
     bencher.iter(|| large_tuple_expression.eval().unwrap());
 }
+
+#[bench]
+fn bench_evaluate_precompiled_expression_with_many_contexts(bencher: &mut Bencher) {
+    let precompiled = build_operator_tree("a * b - c > 5").unwrap();
+    let contexts: Vec<_> = (0..1000)
+        .map(|i| context_map! { "a" => i, "b" => 2, "c" => i % 7 }.unwrap())
+        .collect();
+
+    bencher.iter(|| {
+        for context in &contexts {
+            black_box(precompiled.eval_with_context(context).unwrap());
+        }
+    });
+}
@@ -3,12 +3,18 @@
 //!
 //! This crate implements two basic variants, the `EmptyContext`, that returns `None` for each identifier and cannot be manipulated, and the `HashMapContext`, that stores its mappings in hash maps.
 //! The HashMapContext is type-safe and returns an error if the user tries to assign a value of a different type than before to an identifier.
+//! Additionally, the `ReadOnlyContext` wraps another context and forbids assignments to it, the `ChainedContext` looks up identifiers in a primary context and falls back to a second one, the `DefaultContext` resolves missing variables to a default value, and the `CaseInsensitiveContext` ignores the case of identifiers.
 
+#[cfg(feature = "rand")]
+use std::sync::{Arc, Mutex, PoisonError};
 use std::{collections::HashMap, iter};
 
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, SeedableRng};
+
 use crate::{
     function::Function,
-    value::{value_type::ValueType, Value},
+    value::{value_type::ValueType, FloatType, IntType, Value},
     EvalexprError, EvalexprResult,
 };
 
@@ -22,6 +28,139 @@ pub trait Context {
     /// Calls the function that is linked to the given identifier with the given argument.
     /// If no function with the given identifier is found, this method returns `EvalexprError::FunctionIdentifierNotFound`.
     fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value>;
+
+    /// Calls the function that is linked to the given identifier with the given argument, like `call_function`,
+    /// but passes `context` instead of this context to functions created with `Function::new_with_context`.
+    ///
+    /// Contexts that wrap other contexts use this to make the functions of the wrapped context see the wrapper they are called through.
+    /// By default, `context` is ignored and `call_function` is called.
+    fn call_function_with_context(
+        &self,
+        _context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        self.call_function(identifier, argument)
+    }
+
+    /// Returns how the integer operators `+`, `-` and `*` handle overflow when evaluated with this context.
+    /// By default, an overflow results in an error.
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        IntegerOverflowMode::Checked
+    }
+
+    /// Returns whether the assignment operators `=`, `+=` etc. evaluate to the assigned value when evaluated with this context.
+    /// By default, they evaluate to `Value::Empty`.
+    fn assignments_return_value(&self) -> bool {
+        false
+    }
+
+    /// Returns the maximum amount of elements in a tuple created by the builtin function `range` when evaluated with this context.
+    /// By default, this is `DEFAULT_MAX_RANGE_LENGTH`.
+    fn max_range_length(&self) -> usize {
+        DEFAULT_MAX_RANGE_LENGTH
+    }
+
+    /// Returns the maximum length in bytes of a string created by the builtin functions `str::repeat`, `str::pad_left` and `str::pad_right` when evaluated with this context.
+    /// By default, this is `DEFAULT_MAX_REPEATED_STRING_LENGTH`.
+    fn max_repeated_string_length(&self) -> usize {
+        DEFAULT_MAX_REPEATED_STRING_LENGTH
+    }
+
+    /// Returns the string value that is linked to the given identifier.
+    ///
+    /// Fails with `EvalexprError::VariableIdentifierNotFound` if no value is linked to the identifier, and with `EvalexprError::ExpectedString` if the value is not a string.
+    fn get_string(&self, identifier: &str) -> EvalexprResult<String> {
+        self.get_value(identifier)
+            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(identifier.to_string()))?
+            .as_string()
+    }
+
+    /// Returns the integer value that is linked to the given identifier.
+    ///
+    /// Fails with `EvalexprError::VariableIdentifierNotFound` if no value is linked to the identifier, and with `EvalexprError::ExpectedInt` if the value is not an integer.
+    fn get_int(&self, identifier: &str) -> EvalexprResult<IntType> {
+        self.get_value(identifier)
+            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(identifier.to_string()))?
+            .as_int()
+    }
+
+    /// Returns the float value that is linked to the given identifier.
+    ///
+    /// Fails with `EvalexprError::VariableIdentifierNotFound` if no value is linked to the identifier, and with `EvalexprError::ExpectedFloat` if the value is not a float.
+    fn get_float(&self, identifier: &str) -> EvalexprResult<FloatType> {
+        self.get_value(identifier)
+            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(identifier.to_string()))?
+            .as_float()
+    }
+
+    /// Returns the boolean value that is linked to the given identifier.
+    ///
+    /// Fails with `EvalexprError::VariableIdentifierNotFound` if no value is linked to the identifier, and with `EvalexprError::ExpectedBoolean` if the value is not a boolean.
+    fn get_boolean(&self, identifier: &str) -> EvalexprResult<bool> {
+        self.get_value(identifier)
+            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(identifier.to_string()))?
+            .as_boolean()
+    }
+}
+
+impl<C: Context + ?Sized> Context for &C {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        (**self).get_value(identifier)
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        (**self).call_function(identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        (**self).call_function_with_context(context, identifier, argument)
+    }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        (**self).integer_overflow_mode()
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        (**self).assignments_return_value()
+    }
+
+    fn max_range_length(&self) -> usize {
+        (**self).max_range_length()
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        (**self).max_repeated_string_length()
+    }
+}
+
+/// The default maximum amount of elements in a tuple created by the builtin function `range`.
+pub const DEFAULT_MAX_RANGE_LENGTH: usize = 1 << 24;
+
+/// The default maximum length in bytes of a string created by the builtin functions `str::repeat`, `str::pad_left` and `str::pad_right`.
+pub const DEFAULT_MAX_REPEATED_STRING_LENGTH: usize = 1 << 30;
+
+/// The behavior of the integer operators `+`, `-` and `*` if their result does not fit into an `IntType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum IntegerOverflowMode {
+    /// An overflow results in an error, e.g. `EvalexprError::AdditionError`.
+    Checked,
+    /// An overflow results in the minimum or maximum value of `IntType`, whichever is closer to the exact result.
+    Saturating,
+    /// An overflow wraps around at the boundaries of `IntType`, like fixed-width machine integers do.
+    Wrapping,
+}
+
+impl Default for IntegerOverflowMode {
+    fn default() -> Self {
+        IntegerOverflowMode::Checked
+    }
 }
 
 /// A context that allows to assign to variables.
@@ -105,6 +244,14 @@ pub struct HashMapContext {
     variables: HashMap<String, Value>,
     #[cfg_attr(feature = "serde_support", serde(skip))]
     functions: HashMap<String, Function>,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    integer_overflow_mode: IntegerOverflowMode,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    assignments_return_value: bool,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    max_range_length: Option<usize>,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    max_repeated_string_length: Option<usize>,
 }
 
 impl HashMapContext {
@@ -112,6 +259,164 @@ impl HashMapContext {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Sets how the integer operators `+`, `-` and `*` handle overflow when evaluated with this context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// context.set_integer_overflow_mode(IntegerOverflowMode::Saturating);
+    /// assert_eq!(
+    ///     eval_with_context(&format!("{} + 1", IntType::MAX), &context),
+    ///     Ok(Value::from(IntType::MAX))
+    /// );
+    /// ```
+    pub fn set_integer_overflow_mode(&mut self, mode: IntegerOverflowMode) {
+        self.integer_overflow_mode = mode;
+    }
+
+    /// Sets whether the assignment operators `=`, `+=` etc. evaluate to the assigned value when evaluated with this context, instead of to `Value::Empty`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// assert_eq!(eval_with_context_mut("a = 3", &mut context), Ok(Value::Empty));
+    /// context.set_assignments_return_value(true);
+    /// assert_eq!(eval_with_context_mut("a = 3", &mut context), Ok(Value::from(3)));
+    /// assert_eq!(eval_with_context_mut("b = (a += 1) * 2", &mut context), Ok(Value::from(8)));
+    /// assert_eq!(context.get_value("a"), Some(&Value::from(4)));
+    /// ```
+    pub fn set_assignments_return_value(&mut self, assignments_return_value: bool) {
+        self.assignments_return_value = assignments_return_value;
+    }
+
+    /// Sets the maximum amount of elements in a tuple created by the builtin function `range` when evaluated with this context.
+    /// The default is `DEFAULT_MAX_RANGE_LENGTH`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// context.set_max_range_length(3);
+    /// assert_eq!(
+    ///     eval_with_context("range(0, 3)", &context),
+    ///     Ok(Value::from(vec![Value::from(0), Value::from(1), Value::from(2)]))
+    /// );
+    /// assert!(eval_with_context("range(0, 4)", &context).is_err());
+    /// ```
+    pub fn set_max_range_length(&mut self, max_range_length: usize) {
+        self.max_range_length = Some(max_range_length);
+    }
+
+    /// Sets the maximum length in bytes of a string created by the builtin functions `str::repeat`, `str::pad_left` and `str::pad_right` when evaluated with this context.
+    /// The default is `DEFAULT_MAX_REPEATED_STRING_LENGTH`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// context.set_max_repeated_string_length(4);
+    /// assert_eq!(
+    ///     eval_with_context("str::repeat(\"ab\", 2)", &context),
+    ///     Ok(Value::from("abab"))
+    /// );
+    /// assert!(eval_with_context("str::repeat(\"ab\", 3)", &context).is_err());
+    /// ```
+    pub fn set_max_repeated_string_length(&mut self, max_repeated_string_length: usize) {
+        self.max_repeated_string_length = Some(max_repeated_string_length);
+    }
+
+    /// Removes the variable with the given identifier from this context.
+    /// Returns the value of the removed variable, or `None` if it was not set.
+    ///
+    /// Once removed, the identifier can be assigned a value of any type again.
+    pub fn remove_variable(&mut self, identifier: &str) -> Option<Value> {
+        self.variables.remove(identifier)
+    }
+
+    /// Removes all variables from this context, keeping its functions.
+    ///
+    /// The allocated capacity of this context is kept, so it can be reused between evaluations.
+    pub fn clear_variables(&mut self) {
+        self.variables.clear()
+    }
+
+    /// Removes the function with the given identifier from this context.
+    /// Returns the removed function, or `None` if no function with this identifier was set.
+    ///
+    /// Builtin functions cannot be removed.
+    pub fn remove_function(&mut self, identifier: &str) -> Option<Function> {
+        self.functions.remove(identifier)
+    }
+
+    /// Copies all variables and functions of `other` into this context.
+    ///
+    /// If both contexts contain a variable or a function with the same identifier, the one from `other` replaces the one in this context.
+    /// A variable is replaced even if its new value has a different type.
+    /// The settings of this context, like its integer overflow mode, are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = context_map! { "a" => 1, "b" => 2 }.unwrap();
+    /// let other = context_map! { "b" => "two", "c" => 3 }.unwrap();
+    /// context.extend(&other);
+    /// assert_eq!(
+    ///     eval_with_context("(a, b, c)", &context),
+    ///     Ok(Value::from(vec![Value::from(1), Value::from("two"), Value::from(3)]))
+    /// );
+    /// ```
+    pub fn extend(&mut self, other: &HashMapContext) {
+        self.variables.extend(
+            other
+                .variables
+                .iter()
+                .map(|(identifier, value)| (identifier.clone(), value.clone())),
+        );
+        self.functions.extend(
+            other
+                .functions
+                .iter()
+                .map(|(identifier, function)| (identifier.clone(), function.clone())),
+        );
+    }
+
+    /// Makes the builtin random functions deterministic when evaluated with this context.
+    /// Contexts seeded with the same `seed` produce the same sequence of random values.
+    ///
+    /// This registers functions with the identifiers `random` and `random_int` in this context, replacing any previous functions with these identifiers.
+    /// Clones of this context share the same random number generator.
+    /// Requires the `rand` feature flag.
+    #[cfg(feature = "rand")]
+    pub fn set_random_seed(&mut self, seed: u64) {
+        use crate::function::builtin::{random, random_int};
+
+        let rng = Arc::new(Mutex::new(StdRng::seed_from_u64(seed)));
+        let seeded = |function: fn(&mut StdRng, &Value) -> EvalexprResult<Value>| {
+            let rng = rng.clone();
+            Function::new(move |argument| {
+                function(
+                    &mut rng.lock().unwrap_or_else(PoisonError::into_inner),
+                    argument,
+                )
+            })
+        };
+        self.functions.insert("random".to_string(), seeded(random));
+        self.functions
+            .insert("random_int".to_string(), seeded(random_int));
+    }
 }
 
 impl Context for HashMapContext {
@@ -120,14 +425,40 @@ impl Context for HashMapContext {
     }
 
     fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.call_function_with_context(self, identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
         if let Some(function) = self.functions.get(identifier) {
-            function.call(argument)
+            function.call(context, argument)
         } else {
             Err(EvalexprError::FunctionIdentifierNotFound(
                 identifier.to_string(),
             ))
         }
     }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        self.integer_overflow_mode
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        self.assignments_return_value
+    }
+
+    fn max_range_length(&self) -> usize {
+        self.max_range_length.unwrap_or(DEFAULT_MAX_RANGE_LENGTH)
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        self.max_repeated_string_length
+            .unwrap_or(DEFAULT_MAX_REPEATED_STRING_LENGTH)
+    }
 }
 
 impl ContextWithMutableVariables for HashMapContext {
@@ -173,6 +504,422 @@ impl<'a> IterateVariablesContext<'a> for HashMapContext {
     }
 }
 
+/// A context that forbids assignments, wrapping another context.
+///
+/// All reads and function calls are forwarded to the wrapped context, while assigning to a variable or function results in `EvalexprError::ContextNotMutable`.
+/// This allows to evaluate untrusted expressions with `eval_with_context_mut` and similar functions without them changing the wrapped context.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let mut context = ReadOnlyContext::new(context_map! { "x" => 4 }.unwrap());
+/// assert_eq!(eval_with_context_mut("x + 1", &mut context), Ok(Value::from(5)));
+/// assert_eq!(eval_with_context_mut("x = 5", &mut context), Err(EvalexprError::ContextNotMutable));
+/// assert_eq!(context.get_value("x"), Some(&Value::from(4)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReadOnlyContext<C: Context> {
+    context: C,
+}
+
+impl<C: Context> ReadOnlyContext<C> {
+    /// Constructs a `ReadOnlyContext` that forbids assignments to the given context.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Returns a reference to the wrapped context.
+    pub fn inner(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns the wrapped context.
+    pub fn into_inner(self) -> C {
+        self.context
+    }
+}
+
+impl<C: Context> Context for ReadOnlyContext<C> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.context.get_value(identifier)
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.call_function_with_context(self, identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        self.context
+            .call_function_with_context(context, identifier, argument)
+    }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        self.context.integer_overflow_mode()
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        self.context.assignments_return_value()
+    }
+
+    fn max_range_length(&self) -> usize {
+        self.context.max_range_length()
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        self.context.max_repeated_string_length()
+    }
+}
+
+impl<C: Context> ContextWithMutableVariables for ReadOnlyContext<C> {}
+
+impl<C: Context> ContextWithMutableFunctions for ReadOnlyContext<C> {}
+
+impl<'a, C: Context + IterateVariablesContext<'a>> IterateVariablesContext<'a>
+    for ReadOnlyContext<C>
+{
+    type VariableIterator = C::VariableIterator;
+    type VariableNameIterator = C::VariableNameIterator;
+
+    fn iter_variables(&'a self) -> Self::VariableIterator {
+        self.context.iter_variables()
+    }
+
+    fn iter_variable_names(&'a self) -> Self::VariableNameIterator {
+        self.context.iter_variable_names()
+    }
+}
+
+/// A context that combines a primary context with a fallback context.
+///
+/// Variables and functions are looked up in the primary context first, and in the fallback context if the primary context does not contain them.
+/// Assignments always go to the primary context, such that the fallback context is never changed.
+/// The integer overflow mode is taken from the primary context.
+///
+/// Since `Context` is implemented for references to contexts, a shared base context can be used as fallback by reference.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let defaults = context_map! { "width" => 10, "height" => 20 }.unwrap();
+/// let mut context = ChainedContext::new(HashMapContext::new(), &defaults);
+/// assert_eq!(eval_with_context_mut("width = 15; width * height", &mut context), Ok(Value::from(300)));
+/// assert_eq!(defaults.get_value("width"), Some(&Value::from(10)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ChainedContext<P: Context, F: Context> {
+    primary: P,
+    fallback: F,
+}
+
+impl<P: Context, F: Context> ChainedContext<P, F> {
+    /// Constructs a `ChainedContext` that looks up identifiers in `primary` first and in `fallback` second.
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self { primary, fallback }
+    }
+
+    /// Returns a reference to the primary context.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a mutable reference to the primary context.
+    pub fn primary_mut(&mut self) -> &mut P {
+        &mut self.primary
+    }
+
+    /// Returns a reference to the fallback context.
+    pub fn fallback(&self) -> &F {
+        &self.fallback
+    }
+
+    /// Returns the primary and the fallback context.
+    pub fn into_inner(self) -> (P, F) {
+        (self.primary, self.fallback)
+    }
+}
+
+impl<P: Context, F: Context> Context for ChainedContext<P, F> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.primary
+            .get_value(identifier)
+            .or_else(|| self.fallback.get_value(identifier))
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.call_function_with_context(self, identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        match self
+            .primary
+            .call_function_with_context(context, identifier, argument)
+        {
+            Err(EvalexprError::FunctionIdentifierNotFound(not_found))
+                if not_found == identifier =>
+            {
+                self.fallback
+                    .call_function_with_context(context, identifier, argument)
+            },
+            result => result,
+        }
+    }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        self.primary.integer_overflow_mode()
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        self.primary.assignments_return_value()
+    }
+
+    fn max_range_length(&self) -> usize {
+        self.primary.max_range_length()
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        self.primary.max_repeated_string_length()
+    }
+}
+
+impl<P: ContextWithMutableVariables, F: Context> ContextWithMutableVariables
+    for ChainedContext<P, F>
+{
+    fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+        self.primary.set_value(identifier, value)
+    }
+}
+
+impl<P: ContextWithMutableFunctions, F: Context> ContextWithMutableFunctions
+    for ChainedContext<P, F>
+{
+    fn set_function(&mut self, identifier: String, function: Function) -> EvalexprResult<()> {
+        self.primary.set_function(identifier, function)
+    }
+}
+
+/// A context that resolves variables missing in the wrapped context to a default value.
+///
+/// Reading a variable that the wrapped context does not contain yields the configured default instead of `EvalexprError::VariableIdentifierNotFound`.
+/// Function calls and assignments are forwarded to the wrapped context.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context = context_map! { "a" => 1 }.unwrap();
+/// assert_eq!(eval_with_context("b", &context), Err(EvalexprError::VariableIdentifierNotFound("b".to_string())));
+///
+/// let context = DefaultContext::new(context, Value::from(0));
+/// assert_eq!(eval_with_context("a + b", &context), Ok(Value::from(1)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DefaultContext<C: Context> {
+    context: C,
+    default: Value,
+}
+
+impl<C: Context> DefaultContext<C> {
+    /// Constructs a `DefaultContext` that resolves variables missing in `context` to `default`.
+    pub fn new(context: C, default: Value) -> Self {
+        Self { context, default }
+    }
+
+    /// Returns a reference to the wrapped context.
+    pub fn inner(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns a mutable reference to the wrapped context.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.context
+    }
+
+    /// Returns the value that missing variables resolve to.
+    pub fn default_value(&self) -> &Value {
+        &self.default
+    }
+
+    /// Returns the wrapped context.
+    pub fn into_inner(self) -> C {
+        self.context
+    }
+}
+
+impl<C: Context> Context for DefaultContext<C> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        Some(self.context.get_value(identifier).unwrap_or(&self.default))
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.call_function_with_context(self, identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        self.context
+            .call_function_with_context(context, identifier, argument)
+    }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        self.context.integer_overflow_mode()
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        self.context.assignments_return_value()
+    }
+
+    fn max_range_length(&self) -> usize {
+        self.context.max_range_length()
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        self.context.max_repeated_string_length()
+    }
+}
+
+impl<C: ContextWithMutableVariables> ContextWithMutableVariables for DefaultContext<C> {
+    fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+        self.context.set_value(identifier, value)
+    }
+}
+
+impl<C: ContextWithMutableFunctions> ContextWithMutableFunctions for DefaultContext<C> {
+    fn set_function(&mut self, identifier: String, function: Function) -> EvalexprResult<()> {
+        self.context.set_function(identifier, function)
+    }
+}
+
+/// A context that treats variable and function identifiers case-insensitively, wrapping another context.
+///
+/// All identifiers are converted to lowercase before they are forwarded to the wrapped context, both when reading and when assigning.
+/// Hence, `Temp` and `temp` refer to the same variable.
+/// Identifiers that contain uppercase characters and are set directly in the wrapped context cannot be accessed through this context.
+/// String literals and builtin functions are not affected.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let mut context = CaseInsensitiveContext::new(HashMapContext::new());
+/// assert_eq!(eval_with_context_mut("Temp = 20; TEMP + temp", &mut context), Ok(Value::from(40)));
+/// assert_eq!(context.inner().get_value("temp"), Some(&Value::from(20)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CaseInsensitiveContext<C: Context> {
+    context: C,
+}
+
+impl<C: Context> CaseInsensitiveContext<C> {
+    /// Constructs a `CaseInsensitiveContext` that forwards lowercase identifiers to the given context.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Returns a reference to the wrapped context.
+    pub fn inner(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns the wrapped context.
+    pub fn into_inner(self) -> C {
+        self.context
+    }
+}
+
+impl<C: Context> Context for CaseInsensitiveContext<C> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.context.get_value(&identifier.to_lowercase())
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.call_function_with_context(self, identifier, argument)
+    }
+
+    fn call_function_with_context(
+        &self,
+        context: &dyn Context,
+        identifier: &str,
+        argument: &Value,
+    ) -> EvalexprResult<Value> {
+        let lowercase = identifier.to_lowercase();
+        match self
+            .context
+            .call_function_with_context(context, &lowercase, argument)
+        {
+            Err(EvalexprError::FunctionIdentifierNotFound(not_found)) if not_found == lowercase => {
+                Err(EvalexprError::FunctionIdentifierNotFound(
+                    identifier.to_string(),
+                ))
+            },
+            result => result,
+        }
+    }
+
+    fn integer_overflow_mode(&self) -> IntegerOverflowMode {
+        self.context.integer_overflow_mode()
+    }
+
+    fn assignments_return_value(&self) -> bool {
+        self.context.assignments_return_value()
+    }
+
+    fn max_range_length(&self) -> usize {
+        self.context.max_range_length()
+    }
+
+    fn max_repeated_string_length(&self) -> usize {
+        self.context.max_repeated_string_length()
+    }
+}
+
+impl<C: ContextWithMutableVariables> ContextWithMutableVariables for CaseInsensitiveContext<C> {
+    fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+        self.context.set_value(identifier.to_lowercase(), value)
+    }
+}
+
+impl<C: ContextWithMutableFunctions> ContextWithMutableFunctions for CaseInsensitiveContext<C> {
+    fn set_function(&mut self, identifier: String, function: Function) -> EvalexprResult<()> {
+        self.context
+            .set_function(identifier.to_lowercase(), function)
+    }
+}
+
+impl<'a, C: Context + IterateVariablesContext<'a>> IterateVariablesContext<'a>
+    for CaseInsensitiveContext<C>
+{
+    type VariableIterator = C::VariableIterator;
+    type VariableNameIterator = C::VariableNameIterator;
+
+    fn iter_variables(&'a self) -> Self::VariableIterator {
+        self.context.iter_variables()
+    }
+
+    fn iter_variable_names(&'a self) -> Self::VariableNameIterator {
+        self.context.iter_variable_names()
+    }
+}
+
 /// This macro provides a convenient syntax for creating a static context.
 ///
 /// # Examples
@@ -190,16 +937,16 @@ impl<'a> IterateVariablesContext<'a> for HashMapContext {
 #[macro_export]
 macro_rules! context_map {
     // Termination (allow missing comma at the end of the argument list)
-    ( ($ctx:expr) $k:expr => Function::new($($v:tt)*) ) =>
-        { $crate::context_map!(($ctx) $k => Function::new($($v)*),) };
+    ( ($ctx:expr) $k:expr => Function::$constructor:ident($($v:tt)*) ) =>
+        { $crate::context_map!(($ctx) $k => Function::$constructor($($v)*),) };
     ( ($ctx:expr) $k:expr => $v:expr ) =>
         { $crate::context_map!(($ctx) $k => $v,)  };
     // Termination
     ( ($ctx:expr) ) => { Ok(()) };
 
-    // The user has to specify a literal 'Function::new' in order to create a function
-    ( ($ctx:expr) $k:expr => Function::new($($v:tt)*) , $($tt:tt)*) => {{
-        $crate::ContextWithMutableFunctions::set_function($ctx, $k.into(), $crate::Function::new($($v)*))
+    // The user has to specify a literal 'Function::new', 'Function::new_fixed' or 'Function::new_with_context' in order to create a function
+    ( ($ctx:expr) $k:expr => Function::$constructor:ident($($v:tt)*) , $($tt:tt)*) => {{
+        $crate::ContextWithMutableFunctions::set_function($ctx, $k.into(), $crate::Function::$constructor($($v)*))
             .and($crate::context_map!(($ctx) $($tt)*))
     }};
     // add a value, and chain the eventual error with the ones in the next values
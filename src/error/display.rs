@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::EvalexprError;
+use crate::{error::ParseError, EvalexprError};
 
 impl fmt::Display for EvalexprError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -69,12 +69,22 @@ impl fmt::Display for EvalexprError {
             ),
             UnmatchedLBrace => write!(f, "Found an unmatched opening parenthesis '('."),
             UnmatchedRBrace => write!(f, "Found an unmatched closing parenthesis ')'."),
-            MissingOperatorOutsideOfBrace { .. } => write!(
+            UnmatchedLBracket => write!(f, "Found an unmatched opening bracket '['."),
+            UnmatchedRBracket => write!(f, "Found an unmatched closing bracket ']'."),
+            MissingOperatorOutsideOfBrace => write!(
                 f,
                 "Found an opening parenthesis that is preceded by something that does not take \
                  any arguments on the right, or found a closing parenthesis that is succeeded by \
                  something that does not take any arguments on the left."
             ),
+            UnmatchedConditional => write!(
+                f,
+                "Found a '?' without a matching ':' or a ':' without a matching '?'."
+            ),
+            ChainedComparison => write!(
+                f,
+                "Found chained comparisons like 'a < b < c'. Use 'a < b && b < c' instead."
+            ),
             UnmatchedPartialToken { first, second } => {
                 if let Some(second) = second {
                     write!(
@@ -114,7 +124,33 @@ impl fmt::Display for EvalexprError {
             ),
             ContextNotMutable => write!(f, "Cannot manipulate context"),
             IllegalEscapeSequence(string) => write!(f, "Illegal escape sequence: {}", string),
+            IntLiteralOutOfRange(literal) => {
+                write!(f, "Integer literal out of range: {}", literal)
+            },
+            MisplacedDigitSeparator(literal) => {
+                write!(
+                    f,
+                    "Misplaced digit separator in number literal: {}",
+                    literal
+                )
+            },
+            MaxDepthExceeded { max_depth } => write!(
+                f,
+                "The expression is nested deeper than the maximum depth of {}",
+                max_depth
+            ),
+            BudgetExceeded { budget } => write!(
+                f,
+                "The evaluation exceeded its budget of {} evaluated operators",
+                budget
+            ),
             CustomMessage(message) => write!(f, "Error: {}", message),
         }
     }
 }
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Error at position {}: {}", self.position(), self.error())
+    }
+}
@@ -129,13 +129,27 @@ pub enum EvalexprError {
     /// A closing brace without a matching opening brace was found.
     UnmatchedRBrace,
 
+    /// An opening bracket without a matching closing bracket was found.
+    UnmatchedLBracket,
+
+    /// A closing bracket without a matching opening bracket was found.
+    UnmatchedRBracket,
+
     /// Left of an opening brace or right of a closing brace is a token that does not expect the brace next to it.
     /// For example, writing `4(5)` would yield this error, as the `4` does not have any operands.
     MissingOperatorOutsideOfBrace,
 
+    /// A `?` without a matching `:` or a `:` without a matching `?` was found.
+    /// Conditionals nested in the first branch of another conditional need to be surrounded by parentheses.
+    UnmatchedConditional,
+
+    /// Comparisons were chained without parentheses, like in `a < b < c`.
+    /// Such a chain would compare the boolean result of the first comparison, so it is rejected.
+    /// Use `a < b && b < c` instead.
+    ChainedComparison,
+
     /// A `PartialToken` is unmatched, such that it cannot be combined into a full `Token`.
-    /// This happens if for example a single `=` is found, surrounded by whitespace.
-    /// It is not a token, but it is part of the string representation of some tokens.
+    /// Every partial token is currently combined into a token on its own, so the tokenizer no longer produces this error.
     UnmatchedPartialToken {
         /// The unmatched partial token.
         first: PartialToken,
@@ -203,6 +217,24 @@ pub enum EvalexprError {
     /// An escape sequence within a string literal is illegal.
     IllegalEscapeSequence(String),
 
+    /// A hexadecimal, octal or binary integer literal does not fit into an `IntType`.
+    IntLiteralOutOfRange(String),
+
+    /// A number literal contains an underscore that does not separate two digits, like in `1__0` or `5_`.
+    MisplacedDigitSeparator(String),
+
+    /// An expression is nested deeper than allowed.
+    MaxDepthExceeded {
+        /// The maximum allowed depth of the operator tree.
+        max_depth: usize,
+    },
+
+    /// An evaluation needed to evaluate more operators than its budget allowed.
+    BudgetExceeded {
+        /// The maximum allowed amount of evaluated operators.
+        budget: usize,
+    },
+
     /// A custom error explained by its message.
     CustomMessage(String),
 }
@@ -286,13 +318,6 @@ impl EvalexprError {
         }
     }
 
-    pub(crate) fn unmatched_partial_token(
-        first: PartialToken,
-        second: Option<PartialToken>,
-    ) -> Self {
-        EvalexprError::UnmatchedPartialToken { first, second }
-    }
-
     pub(crate) fn addition_error(augend: Value, addend: Value) -> Self {
         EvalexprError::AdditionError { augend, addend }
     }
@@ -327,6 +352,13 @@ impl EvalexprError {
     pub fn invalid_regex(regex: String, message: String) -> Self {
         EvalexprError::InvalidRegex { regex, message }
     }
+
+    /// Constructs `EvalexprError::CustomMessage(message)`.
+    ///
+    /// This is meant for user-defined functions that want to report their own errors.
+    pub fn custom<S: Into<String>>(message: S) -> Self {
+        EvalexprError::CustomMessage(message.into())
+    }
 }
 
 /// Returns `Ok(())` if the actual and expected parameters are equal, and `Err(Error::WrongOperatorArgumentAmount)` otherwise.
@@ -367,6 +399,47 @@ impl std::error::Error for EvalexprError {}
 /// Standard result type used by this crate.
 pub type EvalexprResult<T> = Result<T, EvalexprError>;
 
+/// An error that occurred while parsing an expression, together with the position in the expression where it was detected.
+///
+/// This is returned by the functions that report where parsing failed, like `build_operator_tree_with_position`.
+/// It converts into the contained `EvalexprError`, so the position can be dropped with the `?` operator.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    error: EvalexprError,
+    position: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(error: EvalexprError, position: usize) -> Self {
+        ParseError { error, position }
+    }
+
+    /// Returns the error that occurred.
+    pub fn error(&self) -> &EvalexprError {
+        &self.error
+    }
+
+    /// Returns the byte position in the expression string where the error was detected.
+    /// This is the start of the token that caused the error,
+    /// or the length of the expression if the error was only detected at its end.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the error that occurred, discarding its position.
+    pub fn into_error(self) -> EvalexprError {
+        self.error
+    }
+}
+
+impl From<ParseError> for EvalexprError {
+    fn from(error: ParseError) -> Self {
+        error.error
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 #[cfg(test)]
 mod tests {
     use crate::{EvalexprError, Value, ValueType};
@@ -0,0 +1,214 @@
+//! A plain serde representation of `Value` for interoperability with self-describing formats like JSON.
+//!
+//! By default, `Value` is (de)serialized as an enum, i.e. each value is tagged with the name of its variant.
+//! This module instead maps each variant to the closest type of the [serde data model](https://serde.rs/data-model.html):
+//!
+//! | Value             | Serde type     | JSON           |
+//! |-------------------|----------------|----------------|
+//! | `Value::String`   | `string`       | string         |
+//! | `Value::Float`    | `f64`          | number         |
+//! | `Value::Int`      | `i64`          | number         |
+//! | `Value::Boolean`  | `bool`         | boolean        |
+//! | `Value::Tuple`    | `seq`          | array          |
+//! | `Value::Empty`    | `unit`         | null           |
+//!
+//! When deserializing, integers that do not fit into an `IntType` become floats, and `none` becomes `Value::Empty`.
+//! Formats that do not distinguish integers from floats may deserialize a `Value::Float` with an integral value as a `Value::Int`.
+//!
+//! The module is meant to be used with serde's `with` attribute:
+//!
+//! ```rust
+//! # #[cfg(feature = "serde_support")] {
+//! use evalexpr::*;
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Setting {
+//!     #[serde(with = "evalexpr::untagged_value")]
+//!     value: Value,
+//! }
+//!
+//! let setting = Setting { value: Value::Tuple(vec![Value::Int(1), Value::Float(2.5)]) };
+//! assert_eq!(ron::ser::to_string(&setting).unwrap(), "(value:[1,2.5])");
+//! # }
+//! ```
+
+use crate::{
+    value::{FloatType, IntType},
+    Value,
+};
+use serde::{
+    de::{SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{convert::TryFrom, fmt};
+
+/// Serializes a `Value` in the plain representation of this module.
+pub fn serialize<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Value::String(string) => serializer.serialize_str(string),
+        Value::Float(float) => serializer.serialize_f64(*float),
+        Value::Int(int) => serializer.serialize_i64(*int),
+        Value::Boolean(boolean) => serializer.serialize_bool(*boolean),
+        Value::Tuple(tuple) => {
+            let mut seq = serializer.serialize_seq(Some(tuple.len()))?;
+            for element in tuple {
+                seq.serialize_element(&Untagged(element))?;
+            }
+            seq.end()
+        },
+        Value::Empty => serializer.serialize_unit(),
+    }
+}
+
+/// Deserializes a `Value` from the plain representation of this module.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(ValueVisitor)
+}
+
+struct Untagged<'a>(&'a Value);
+
+impl<'a> Serialize for Untagged<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(self.0, serializer)
+    }
+}
+
+struct UntaggedOwned(Value);
+
+impl<'de> Deserialize<'de> for UntaggedOwned {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer).map(UntaggedOwned)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a string, number, boolean, sequence or unit value that can be converted to an `evalexpr` value"
+        )
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(IntType::try_from(v)
+            .map(Value::Int)
+            .unwrap_or(Value::Float(v as FloatType)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(IntType::try_from(v)
+            .map(Value::Int)
+            .unwrap_or(Value::Float(v as FloatType)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(v as FloatType))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Empty)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Empty)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut tuple = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(UntaggedOwned(element)) = seq.next_element()? {
+            tuple.push(element);
+        }
+        Ok(Value::Tuple(tuple))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "super")]
+        value: Value,
+    }
+
+    fn round_trip(value: Value, expected: &str) {
+        let wrapper = Wrapper { value };
+        let serialized = ron::ser::to_string(&wrapper).unwrap();
+        assert_eq!(serialized, format!("(value:{})", expected));
+        assert_eq!(ron::de::from_str::<Wrapper>(&serialized), Ok(wrapper));
+    }
+
+    #[test]
+    fn test_untagged_value_round_trip() {
+        round_trip(Value::from("a \"string\""), "\"a \\\"string\\\"\"");
+        round_trip(Value::Float(3.0), "3.0");
+        round_trip(Value::Float(-0.5), "-0.5");
+        round_trip(Value::Int(-3), "-3");
+        round_trip(Value::Boolean(true), "true");
+        round_trip(Value::Empty, "()");
+        round_trip(
+            Value::Tuple(vec![
+                Value::Int(1),
+                Value::Tuple(vec![Value::from("b"), Value::Empty]),
+                Value::Tuple(vec![]),
+            ]),
+            "[1,[\"b\",()],[]]",
+        );
+    }
+
+    #[test]
+    fn test_untagged_value_deserialize() {
+        let deserialize = |string: &str| {
+            ron::de::from_str::<Wrapper>(&format!("(value:{})", string)).map(|w| w.value)
+        };
+
+        assert_eq!(deserialize("Some(5)"), Ok(Value::Int(5)));
+        assert_eq!(deserialize("None"), Ok(Value::Empty));
+        assert_eq!(
+            deserialize("18446744073709551615"),
+            Ok(Value::Float(18446744073709551615.0))
+        );
+        assert!(deserialize("{\"a\": 1}").is_err());
+    }
+}
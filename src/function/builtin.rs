@@ -1,11 +1,16 @@
 #[cfg(feature = "regex_support")]
-use regex::Regex;
+use crate::function::regex_cache::cached_regex;
 
 use crate::{
-    value::{FloatType, IntType},
-    EvalexprError, Function, Value, ValueType,
+    value::{ordering::compare_numbers, FloatType, IntType, TupleType},
+    Context, EvalexprError, EvalexprResult, Function, Value, ValueType,
+};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    iter,
+    ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr},
 };
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
 
 macro_rules! simple_math {
     ($func:ident) => {
@@ -29,6 +34,323 @@ fn float_is(func: fn(FloatType) -> bool) -> Option<Function> {
     }))
 }
 
+fn value_is(func: fn(&Value) -> bool) -> Option<Function> {
+    Some(Function::new(move |argument| Ok(func(argument).into())))
+}
+
+fn str_predicate(func: fn(&str, &str) -> bool) -> Option<Function> {
+    Some(Function::new(move |argument| {
+        let arguments = argument.as_fixed_len_tuple(2)?;
+
+        let subject = arguments[0].as_string()?;
+        let pattern = arguments[1].as_string()?;
+        Ok(func(&subject, &pattern).into())
+    }))
+}
+
+/// Returns the builtin function `str::pad_left` if `left` is true, or `str::pad_right` otherwise.
+fn str_pad(left: bool) -> Option<Function> {
+    Some(Function::new_with_context(move |context, argument| {
+        let function = if left {
+            "str::pad_left"
+        } else {
+            "str::pad_right"
+        };
+        let arguments = argument.as_fixed_len_tuple(3)?;
+
+        let subject = arguments[0].as_string()?;
+        let width = as_usize(&arguments[1], function)?;
+        let fill = arguments[2].as_string()?;
+        let mut fill_chars = fill.chars();
+        let fill = match (fill_chars.next(), fill_chars.next()) {
+            (Some(fill), None) => fill,
+            _ => {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "{} expected a single character to fill with, but got {:?}",
+                    function, fill
+                )))
+            },
+        };
+
+        let padding = width.saturating_sub(subject.chars().count());
+        let max_length = context.max_repeated_string_length();
+        if padding
+            .checked_mul(fill.len_utf8())
+            .and_then(|length| length.checked_add(subject.len()))
+            .map_or(true, |length| length > max_length)
+        {
+            return Err(EvalexprError::CustomMessage(format!(
+                "{} result would exceed the maximum string length of {} bytes",
+                function, max_length
+            )));
+        }
+
+        let padding: String = iter::repeat(fill).take(padding).collect();
+        Ok(Value::String(if left {
+            padding + &subject
+        } else {
+            subject + &padding
+        }))
+    }))
+}
+
+/// Evaluates the builtin function `random`, drawing from the given random number generator.
+#[cfg(feature = "rand")]
+pub(crate) fn random<R: rand::Rng + ?Sized>(
+    rng: &mut R,
+    argument: &Value,
+) -> EvalexprResult<Value> {
+    if let Value::Empty = argument {
+        return Ok(Value::Float(rng.gen()));
+    }
+
+    let arguments = argument.as_fixed_len_tuple(2)?;
+    let (min, max) = (arguments[0].as_number()?, arguments[1].as_number()?);
+    if min <= max && min.is_finite() && max.is_finite() {
+        // Interpolate instead of computing `max - min`, which may overflow
+        let t: FloatType = rng.gen();
+        Ok(Value::Float(min * (1.0 - t) + max * t))
+    } else {
+        Err(EvalexprError::CustomMessage(format!(
+            "random expected finite min <= max, but got min = {} and max = {}",
+            min, max
+        )))
+    }
+}
+
+/// Evaluates the builtin function `random_int`, drawing from the given random number generator.
+#[cfg(feature = "rand")]
+pub(crate) fn random_int<R: rand::Rng + ?Sized>(
+    rng: &mut R,
+    argument: &Value,
+) -> EvalexprResult<Value> {
+    let arguments = argument.as_fixed_len_tuple(2)?;
+    let (min, max) = (arguments[0].as_int()?, arguments[1].as_int()?);
+    if min <= max {
+        Ok(Value::Int(rng.gen_range(min..=max)))
+    } else {
+        Err(EvalexprError::CustomMessage(format!(
+            "random_int expected min <= max, but got min = {} and max = {}",
+            min, max
+        )))
+    }
+}
+
+/// Calls the function with the given identifier like a function call in an expression does,
+/// i.e. looks it up in the context first and among the builtin functions second.
+pub(crate) fn call_function_by_name(
+    context: &dyn Context,
+    identifier: &str,
+    argument: &Value,
+) -> EvalexprResult<Value> {
+    match context.call_function(identifier, argument) {
+        Err(EvalexprError::FunctionIdentifierNotFound(_)) => {
+            if let Some(builtin_function) = builtin_function(identifier) {
+                builtin_function.call(context, argument)
+            } else {
+                Err(EvalexprError::FunctionIdentifierNotFound(
+                    identifier.to_string(),
+                ))
+            }
+        },
+        result => result,
+    }
+}
+
+/// Converts an integer argument of the builtin function `function` to a `usize`,
+/// or returns `Err` if the argument is not a non-negative `Value::Int`.
+fn as_usize(argument: &Value, function: &str) -> EvalexprResult<usize> {
+    let int = argument.as_int()?;
+    usize::try_from(int).map_err(|_| {
+        EvalexprError::CustomMessage(format!(
+            "{} expected a non-negative integer, but got {}",
+            function, int
+        ))
+    })
+}
+
+/// Returns the element at `index` of the tuple, or `Err` if the index is out of bounds.
+fn tuple_element(tuple: &[Value], index: usize, function: &str) -> EvalexprResult<Value> {
+    tuple.get(index).cloned().ok_or_else(|| {
+        EvalexprError::CustomMessage(format!(
+            "{} index {} is out of bounds for a tuple of length {}",
+            function,
+            index,
+            tuple.len()
+        ))
+    })
+}
+
+/// Returns the character at the character `index` of the string as a string, or `Err` if the index is out of bounds.
+fn string_character(string: &str, index: usize, function: &str) -> EvalexprResult<Value> {
+    if let Some(c) = string.chars().nth(index) {
+        Ok(Value::from(c.to_string()))
+    } else {
+        Err(EvalexprError::CustomMessage(format!(
+            "{} index {} is out of bounds for a string of {} characters",
+            function,
+            index,
+            string.chars().count()
+        )))
+    }
+}
+
+/// Evaluates the indexing operator `collection[index]`,
+/// which works like `get` for tuples and like `str::char_at` for strings.
+pub(crate) fn index(collection: &Value, index: &Value) -> EvalexprResult<Value> {
+    match collection {
+        Value::Tuple(tuple) => tuple_element(tuple, as_usize(index, "[]")?, "[]"),
+        Value::String(string) => string_character(string, as_usize(index, "[]")?, "[]"),
+        value => Err(EvalexprError::type_error(
+            value.clone(),
+            vec![ValueType::String, ValueType::Tuple],
+        )),
+    }
+}
+
+/// Converts the given tuple of numbers to floats,
+/// or returns `Err` if the tuple is empty or contains a value that is not a number.
+fn as_non_empty_numbers(argument: &Value, function: &str) -> EvalexprResult<Vec<FloatType>> {
+    let numbers = argument
+        .as_tuple()?
+        .iter()
+        .map(Value::as_number)
+        .collect::<EvalexprResult<Vec<_>>>()?;
+    if numbers.is_empty() {
+        Err(EvalexprError::CustomMessage(format!(
+            "{} expected a non-empty tuple",
+            function
+        )))
+    } else {
+        Ok(numbers)
+    }
+}
+
+/// Returns the arguments of the builtin function `function`, which accepts one or more arguments,
+/// or returns `Err` if there are no arguments.
+fn as_non_empty_arguments(argument: &Value, function: &str) -> EvalexprResult<TupleType> {
+    let arguments = match argument {
+        Value::Tuple(tuple) => tuple.clone(),
+        Value::Empty => Vec::new(),
+        argument => vec![argument.clone()],
+    };
+    if arguments.is_empty() {
+        Err(EvalexprError::CustomMessage(format!(
+            "{} expected at least one argument",
+            function
+        )))
+    } else {
+        Ok(arguments)
+    }
+}
+
+/// Returns the elements of the tuple `argument`, or an empty tuple if `argument` is `()`.
+/// An empty tuple cannot be written in an expression, as `()` evaluates to `Value::Empty`.
+fn as_tuple_or_empty(argument: &Value) -> EvalexprResult<TupleType> {
+    match argument {
+        Value::Empty => Ok(TupleType::new()),
+        argument => argument.as_tuple(),
+    }
+}
+
+/// Replaces the `{}` placeholders in `format` with the given values, as done by the builtin function `str::format`.
+/// Strings are inserted without quotes, and `{{` and `}}` are replaced by literal braces.
+fn format_string(format: &str, values: &[Value]) -> EvalexprResult<String> {
+    let mut result = String::with_capacity(format.len());
+    let mut values_iter = values.iter();
+    let mut placeholders = 0;
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                result.push(c);
+            },
+            ('{', Some('}')) => {
+                chars.next();
+                placeholders += 1;
+                match values_iter.next() {
+                    Some(Value::String(string)) => result.push_str(string),
+                    Some(value) => result.push_str(&value.to_string()),
+                    None => {},
+                }
+            },
+            ('{', _) | ('}', _) => {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "str::format found an unmatched {:?} in the format string {:?}",
+                    c, format
+                )))
+            },
+            _ => result.push(c),
+        }
+    }
+
+    if placeholders == values.len() {
+        Ok(result)
+    } else {
+        Err(EvalexprError::CustomMessage(format!(
+            "str::format expected {} arguments for the placeholders in {:?}, but got {}",
+            placeholders,
+            format,
+            values.len()
+        )))
+    }
+}
+
+fn parse_int(string: &str) -> EvalexprResult<Value> {
+    string.parse().map(Value::Int).map_err(|_| {
+        EvalexprError::CustomMessage(format!("Cannot parse {:?} as an integer", string))
+    })
+}
+
+fn parse_float(string: &str) -> EvalexprResult<Value> {
+    string
+        .parse()
+        .map(Value::Float)
+        .map_err(|_| EvalexprError::CustomMessage(format!("Cannot parse {:?} as a float", string)))
+}
+
+/// Returns the minimum or maximum of the given non-empty numbers, depending on whether `ordering` is `Less` or `Greater`.
+///
+/// The result keeps the type of the extreme argument, so if all arguments are integers, no conversion to float happens.
+/// If an integer and a float are equal, the float is preferred.
+/// NaN arguments are ignored, unless all arguments are NaN.
+fn extremum(arguments: &[Value], ordering: Ordering) -> EvalexprResult<Value> {
+    let mut result: Option<&Value> = None;
+    for argument in arguments {
+        if !argument.is_number() {
+            return Err(EvalexprError::expected_number(argument.clone()));
+        }
+
+        result = match result {
+            Some(current) => match compare_numbers(argument, current) {
+                Some(Ordering::Equal) if argument.is_float() => Some(argument),
+                Some(argument_ordering) if argument_ordering == ordering => Some(argument),
+                Some(_) => Some(current),
+                // Prefer any number over NaN
+                None if matches!(current, Value::Float(float) if float.is_nan()) => Some(argument),
+                None => Some(current),
+            },
+            None => Some(argument),
+        };
+    }
+
+    // `arguments` is not empty
+    Ok(result.cloned().unwrap_or(Value::Empty))
+}
+
+/// Computes the non-negative greatest common divisor of two integers, without overflowing for `IntType::MIN`.
+fn gcd(a: IntType, b: IntType) -> i128 {
+    let (mut a, mut b) = (i128::from(a).abs(), i128::from(b).abs());
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
 macro_rules! int_function {
     ($func:ident) => {
         Some(Function::new(|argument| {
@@ -57,6 +379,9 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         "math::exp2" => simple_math!(exp2),
         // Pow
         "math::pow" => simple_math!(powf, 2),
+        // Angles
+        "math::to_radians" => simple_math!(to_radians),
+        "math::to_degrees" => simple_math!(to_degrees),
         // Cos
         "math::cos" => simple_math!(cos),
         "math::acos" => simple_math!(acos),
@@ -78,10 +403,173 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         "math::cbrt" => simple_math!(cbrt),
         // Hypotenuse
         "math::hypot" => simple_math!(hypot, 2),
+        "math::signum" => Some(Function::new(|argument| match argument {
+            Value::Int(int) => Ok(Value::Int(int.signum())),
+            Value::Float(float) => Ok(Value::Float(float.signum())),
+            _ => Err(EvalexprError::expected_number(argument.clone())),
+        })),
+        "math::clamp" => Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(3)?;
+            if let (Value::Int(value), Value::Int(min), Value::Int(max)) =
+                (&tuple[0], &tuple[1], &tuple[2])
+            {
+                if min > max {
+                    return Err(EvalexprError::CustomMessage(format!(
+                        "math::clamp expected min <= max, but got min = {} and max = {}",
+                        min, max
+                    )));
+                }
+                Ok(Value::Int(*value.max(min).min(max)))
+            } else {
+                let (value, min, max) = (
+                    tuple[0].as_number()?,
+                    tuple[1].as_number()?,
+                    tuple[2].as_number()?,
+                );
+                if min > max {
+                    return Err(EvalexprError::CustomMessage(format!(
+                        "math::clamp expected min <= max, but got min = {} and max = {}",
+                        min, max
+                    )));
+                }
+                if value.is_nan() || (min <= value && value <= max) {
+                    // Keep the type of a value that is already within the bounds, and propagate `NaN`
+                    Ok(tuple[0].clone())
+                } else {
+                    Ok(Value::Float(value.max(min).min(max)))
+                }
+            }
+        })),
+        // Aggregates
+        "math::sum" => Some(Function::new(|argument| {
+            let arguments = as_tuple_or_empty(argument)?;
+
+            if arguments.iter().all(Value::is_int) {
+                let mut sum: IntType = 0;
+                for argument in &arguments {
+                    sum = sum.checked_add(argument.as_int()?).ok_or_else(|| {
+                        EvalexprError::addition_error(Value::Int(sum), argument.clone())
+                    })?;
+                }
+                Ok(Value::Int(sum))
+            } else {
+                let mut sum: FloatType = 0.0;
+                for argument in &arguments {
+                    sum += argument.as_number()?;
+                }
+                Ok(Value::Float(sum))
+            }
+        })),
+        "math::product" => Some(Function::new(|argument| {
+            let arguments = as_tuple_or_empty(argument)?;
+
+            if arguments.iter().all(Value::is_int) {
+                let mut product: IntType = 1;
+                for argument in &arguments {
+                    product = product.checked_mul(argument.as_int()?).ok_or_else(|| {
+                        EvalexprError::multiplication_error(Value::Int(product), argument.clone())
+                    })?;
+                }
+                Ok(Value::Int(product))
+            } else {
+                let mut product: FloatType = 1.0;
+                for argument in &arguments {
+                    product *= argument.as_number()?;
+                }
+                Ok(Value::Float(product))
+            }
+        })),
+        "math::mean" => Some(Function::new(|argument| {
+            let numbers = as_non_empty_numbers(argument, "math::mean")?;
+            Ok(Value::Float(
+                numbers.iter().sum::<FloatType>() / numbers.len() as FloatType,
+            ))
+        })),
+        "math::median" => Some(Function::new(|argument| {
+            let mut numbers = as_non_empty_numbers(argument, "math::median")?;
+            if numbers.iter().any(|number| number.is_nan()) {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "math::median expected numbers that are not NaN, but got {}",
+                    argument
+                )));
+            }
+            // Unwrap cannot fail because there is no NaN, so all numbers are comparable
+            numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let middle = numbers.len() / 2;
+            if numbers.len() % 2 == 0 {
+                Ok(Value::Float((numbers[middle - 1] + numbers[middle]) / 2.0))
+            } else {
+                Ok(Value::Float(numbers[middle]))
+            }
+        })),
+        // Divisors
+        "math::gcd" => Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (tuple[0].as_int()?, tuple[1].as_int()?);
+            IntType::try_from(gcd(a, b)).map(Value::Int).map_err(|_| {
+                EvalexprError::CustomMessage(format!(
+                    "The greatest common divisor of {} and {} is out of range",
+                    a, b
+                ))
+            })
+        })),
+        "math::lcm" => Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (tuple[0].as_int()?, tuple[1].as_int()?);
+            if a == 0 || b == 0 {
+                return Ok(Value::Int(0));
+            }
+            (i128::from(a).abs() / gcd(a, b))
+                .checked_mul(i128::from(b).abs())
+                .and_then(|lcm| IntType::try_from(lcm).ok())
+                .map(Value::Int)
+                .ok_or_else(|| {
+                    EvalexprError::multiplication_error(tuple[0].clone(), tuple[1].clone())
+                })
+        })),
+        // Absolute value
+        "math::abs" => Some(Function::new(|argument| match argument {
+            Value::Int(int) => int
+                .checked_abs()
+                .map(Value::Int)
+                .ok_or_else(|| EvalexprError::negation_error(argument.clone())),
+            Value::Float(float) => Ok(Value::Float(float.abs())),
+            _ => Err(EvalexprError::expected_number(argument.clone())),
+        })),
         // Rounding
         "floor" => simple_math!(floor),
         "round" => simple_math!(round),
         "ceil" => simple_math!(ceil),
+        "math::trunc" => simple_math!(trunc),
+        "math::fract" => simple_math!(fract),
+        "math::round_to" => Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(2)?;
+            let (value, digits) = (tuple[0].as_number()?, tuple[1].as_int()?);
+            // Saturate the digits, as rounding to more than `i32::MAX` places is the same as not rounding at all.
+            // The lower bound is `-i32::MAX` such that the digits can be negated.
+            let digits = digits.max((-i32::MAX).into()).min(i32::MAX.into()) as i32;
+            // Round to tens, hundreds, ... via division, as negative powers of ten are not exactly representable.
+            if digits >= 0 {
+                let factor = (10.0 as FloatType).powi(digits);
+                let scaled = value * factor;
+                if scaled.is_finite() {
+                    Ok(Value::Float(scaled.round() / factor))
+                } else {
+                    // The value has no digits that far behind the decimal point, or the factor overflowed
+                    Ok(Value::Float(value))
+                }
+            } else {
+                let factor = (10.0 as FloatType).powi(-digits);
+                if factor.is_finite() {
+                    Ok(Value::Float((value / factor).round() * factor))
+                } else if value.is_finite() {
+                    // Every finite value is closer to zero than to a power of ten this large, keep its sign
+                    Ok(Value::Float(value * 0.0))
+                } else {
+                    Ok(Value::Float(value))
+                }
+            }
+        })),
         // Float special values
         "math::is_nan" => float_is(FloatType::is_nan),
         "math::is_finite" => float_is(FloatType::is_finite),
@@ -99,55 +587,60 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             }
             .into())
         })),
+        "is_string" => value_is(Value::is_string),
+        "is_int" => value_is(Value::is_int),
+        "is_float" => value_is(Value::is_float),
+        "is_number" => value_is(Value::is_number),
+        "is_boolean" => value_is(Value::is_boolean),
+        "is_tuple" => value_is(Value::is_tuple),
+        "is_empty" => value_is(Value::is_empty),
         "min" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut min_int = IntType::max_value();
-            let mut min_float: FloatType = 1.0 / 0.0;
-            debug_assert!(min_float.is_infinite());
-
-            for argument in arguments {
-                if let Value::Float(float) = argument {
-                    min_float = min_float.min(float);
-                } else if let Value::Int(int) = argument {
-                    min_int = min_int.min(int);
-                } else {
-                    return Err(EvalexprError::expected_number(argument));
-                }
-            }
-
-            if (min_int as FloatType) < min_float {
-                Ok(Value::Int(min_int))
-            } else {
-                Ok(Value::Float(min_float))
-            }
+            extremum(&as_non_empty_arguments(argument, "min")?, Ordering::Less)
         })),
         "max" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut max_int = IntType::min_value();
-            let mut max_float: FloatType = -1.0 / 0.0;
-            debug_assert!(max_float.is_infinite());
-
-            for argument in arguments {
-                if let Value::Float(float) = argument {
-                    max_float = max_float.max(float);
-                } else if let Value::Int(int) = argument {
-                    max_int = max_int.max(int);
+            extremum(&as_non_empty_arguments(argument, "max")?, Ordering::Greater)
+        })),
+        "to_int" => Some(Function::new(|argument| match argument {
+            Value::Int(int) => Ok(Value::Int(*int)),
+            Value::Float(float) => {
+                let truncated = float.trunc();
+                // `IntType::MIN` is a power of two and hence exactly representable as `FloatType`.
+                if truncated >= IntType::MIN as FloatType
+                    && truncated < -(IntType::MIN as FloatType)
+                {
+                    Ok(Value::Int(truncated as IntType))
                 } else {
-                    return Err(EvalexprError::expected_number(argument));
+                    Err(EvalexprError::CustomMessage(format!(
+                        "Cannot convert {} to an integer",
+                        float
+                    )))
                 }
-            }
-
-            if (max_int as FloatType) > max_float {
-                Ok(Value::Int(max_int))
-            } else {
-                Ok(Value::Float(max_float))
-            }
+            },
+            Value::String(string) => parse_int(string),
+            _ => Err(EvalexprError::type_error(
+                argument.clone(),
+                vec![ValueType::Int, ValueType::Float, ValueType::String],
+            )),
+        })),
+        "to_float" => Some(Function::new(|argument| match argument {
+            Value::Int(int) => Ok(Value::Float(*int as FloatType)),
+            Value::Float(float) => Ok(Value::Float(*float)),
+            Value::String(string) => parse_float(string),
+            _ => Err(EvalexprError::type_error(
+                argument.clone(),
+                vec![ValueType::Int, ValueType::Float, ValueType::String],
+            )),
         })),
         "if" => Some(Function::new(|argument| {
             let mut arguments = argument.as_fixed_len_tuple(3)?;
             let result_index = if arguments[0].as_boolean()? { 1 } else { 2 };
             Ok(arguments.swap_remove(result_index))
         })),
+        "xor" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_boolean()?, arguments[1].as_boolean()?);
+            Ok(Value::Boolean(a != b))
+        })),
         "len" => Some(Function::new(|argument| {
             if let Ok(subject) = argument.as_string() {
                 Ok(Value::from(subject.len() as IntType))
@@ -160,6 +653,138 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
                 ))
             }
         })),
+        // Tuple functions
+        "get" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let index = as_usize(&arguments[1], "get")?;
+            tuple_element(&arguments[0].as_tuple()?, index, "get")
+        })),
+        "contains" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_tuple()?;
+            Ok(Value::Boolean(subject.contains(&arguments[1])))
+        })),
+        "slice" => Some(Function::new(|argument| {
+            let mut arguments = argument.as_fixed_len_tuple(3)?;
+
+            let start = as_usize(&arguments[1], "slice")?;
+            let end = as_usize(&arguments[2], "slice")?;
+            let mut subject = arguments.swap_remove(0).into_tuple()?;
+            if start <= end && end <= subject.len() {
+                subject.truncate(end);
+                Ok(Value::Tuple(subject.split_off(start)))
+            } else {
+                Err(EvalexprError::CustomMessage(format!(
+                    "slice bounds {}..{} are invalid for a tuple of length {}",
+                    start,
+                    end,
+                    subject.len()
+                )))
+            }
+        })),
+        "concat" => Some(Function::new(|argument| {
+            let mut result = Vec::new();
+            for element in argument.as_tuple()? {
+                result.extend(element.into_tuple()?);
+            }
+            Ok(Value::Tuple(result))
+        })),
+        "reverse" => Some(Function::new(|argument| {
+            if let Ok(subject) = argument.as_string() {
+                Ok(Value::String(subject.chars().rev().collect()))
+            } else if let Ok(mut subject) = argument.as_tuple() {
+                subject.reverse();
+                Ok(Value::Tuple(subject))
+            } else {
+                Err(EvalexprError::type_error(
+                    argument.clone(),
+                    vec![ValueType::String, ValueType::Tuple],
+                ))
+            }
+        })),
+        "sort" => Some(Function::new(|argument| {
+            let mut subject = argument.as_tuple()?;
+
+            if subject.iter().all(Value::is_string) {
+                let mut strings = subject
+                    .into_iter()
+                    .map(Value::into_string)
+                    .collect::<EvalexprResult<Vec<_>>>()?;
+                strings.sort();
+                subject = strings.into_iter().map(Value::String).collect();
+            } else if subject.iter().all(Value::is_number) {
+                if subject
+                    .iter()
+                    .any(|value| matches!(value, Value::Float(float) if float.is_nan()))
+                {
+                    return Err(EvalexprError::CustomMessage(format!(
+                        "sort expected numbers that are not NaN, but got {}",
+                        argument
+                    )));
+                }
+                // Unwrap cannot fail because numbers other than NaN are totally ordered
+                subject.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            } else {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "sort expected a tuple of only numbers or only strings, but got {}",
+                    argument
+                )));
+            }
+            Ok(Value::Tuple(subject))
+        })),
+        "range" => Some(Function::new_with_context(|context, argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let (start, end) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            let max_length = context.max_range_length();
+            if end as i128 - start as i128 > max_length as i128 {
+                return Err(EvalexprError::CustomMessage(format!(
+                    "range result would exceed the maximum tuple length of {} elements",
+                    max_length
+                )));
+            }
+            Ok(Value::Tuple((start..end).map(Value::Int).collect()))
+        })),
+        "map" => Some(Function::new_with_context(|context, argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_tuple()?;
+            let function = arguments[1].as_string()?;
+            subject
+                .iter()
+                .map(|element| call_function_by_name(context, &function, element))
+                .collect::<EvalexprResult<_>>()
+                .map(Value::Tuple)
+        })),
+        "filter" => Some(Function::new_with_context(|context, argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_tuple()?;
+            let predicate = arguments[1].as_string()?;
+            let mut result = Vec::new();
+            for element in subject {
+                if call_function_by_name(context, &predicate, &element)?.as_boolean()? {
+                    result.push(element);
+                }
+            }
+            Ok(Value::Tuple(result))
+        })),
+        "reduce" => Some(Function::new_with_context(|context, argument| {
+            let mut arguments = argument.as_fixed_len_tuple(3)?;
+
+            let function = arguments[1].as_string()?;
+            let mut accumulator = arguments.swap_remove(2);
+            for element in arguments.swap_remove(0).into_tuple()? {
+                accumulator = call_function_by_name(
+                    context,
+                    &function,
+                    &Value::Tuple(vec![accumulator, element]),
+                )?;
+            }
+            Ok(accumulator)
+        })),
         // String functions
         #[cfg(feature = "regex_support")]
         "str::regex_matches" => Some(Function::new(|argument| {
@@ -167,13 +792,8 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
 
             let subject = arguments[0].as_string()?;
             let re_str = arguments[1].as_string()?;
-            match Regex::new(&re_str) {
-                Ok(re) => Ok(Value::Boolean(re.is_match(&subject))),
-                Err(err) => Err(EvalexprError::invalid_regex(
-                    re_str.to_string(),
-                    format!("{}", err),
-                )),
-            }
+            let re = cached_regex(&re_str)?;
+            Ok(Value::Boolean(re.is_match(&subject)))
         })),
         #[cfg(feature = "regex_support")]
         "str::regex_replace" => Some(Function::new(|argument| {
@@ -182,15 +802,34 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             let subject = arguments[0].as_string()?;
             let re_str = arguments[1].as_string()?;
             let repl = arguments[2].as_string()?;
-            match Regex::new(&re_str) {
-                Ok(re) => Ok(Value::String(
-                    re.replace_all(&subject, repl.as_str()).to_string(),
-                )),
-                Err(err) => Err(EvalexprError::invalid_regex(
-                    re_str.to_string(),
-                    format!("{}", err),
-                )),
-            }
+            let re = cached_regex(&re_str)?;
+            Ok(Value::String(
+                re.replace_all(&subject, repl.as_str()).to_string(),
+            ))
+        })),
+        #[cfg(feature = "regex_support")]
+        "str::regex_captures" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let re_str = arguments[1].as_string()?;
+            let re = cached_regex(&re_str)?;
+            Ok(Value::Tuple(match re.captures(&subject) {
+                Some(captures) => captures
+                    .iter()
+                    .map(|group| group.map_or(Value::Empty, |group| group.as_str().into()))
+                    .collect(),
+                None => Vec::new(),
+            }))
+        })),
+        #[cfg(feature = "regex_support")]
+        "str::regex_split" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let re_str = arguments[1].as_string()?;
+            let re = cached_regex(&re_str)?;
+            Ok(Value::Tuple(re.split(&subject).map(Value::from).collect()))
         })),
         "str::to_lowercase" => Some(Function::new(|argument| {
             let subject = argument.as_string()?;
@@ -204,13 +843,111 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             let subject = argument.as_string()?;
             Ok(Value::from(subject.trim()))
         })),
+        "str::split" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let separator = arguments[1].as_string()?;
+            Ok(Value::Tuple(
+                subject.split(separator.as_str()).map(Value::from).collect(),
+            ))
+        })),
+        "str::join" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = as_tuple_or_empty(&arguments[0])?;
+            let separator = arguments[1].as_string()?;
+            let strings = subject
+                .into_iter()
+                .map(Value::into_string)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::from(strings.join(&separator)))
+        })),
+        "str::contains" => str_predicate(|subject, pattern| subject.contains(pattern)),
+        "str::starts_with" => str_predicate(|subject, pattern| subject.starts_with(pattern)),
+        "str::ends_with" => str_predicate(|subject, pattern| subject.ends_with(pattern)),
+        "str::char_count" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::Int(subject.chars().count() as IntType))
+        })),
+        "str::index_of" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let needle = arguments[1].as_string()?;
+            Ok(Value::Int(match subject.find(needle.as_str()) {
+                Some(byte_index) => subject[..byte_index].chars().count() as IntType,
+                None => -1,
+            }))
+        })),
+        "str::replace" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+
+            let subject = arguments[0].as_string()?;
+            let from = arguments[1].as_string()?;
+            let to = arguments[2].as_string()?;
+            Ok(Value::from(subject.replace(from.as_str(), &to)))
+        })),
+        "str::substring" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+
+            let subject = arguments[0].as_string()?;
+            let start = as_usize(&arguments[1], "str::substring")?;
+            let length = as_usize(&arguments[2], "str::substring")?;
+            Ok(Value::String(
+                subject.chars().skip(start).take(length).collect(),
+            ))
+        })),
+        "str::char_at" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let index = as_usize(&arguments[1], "str::char_at")?;
+            string_character(&subject, index, "str::char_at")
+        })),
+        "str::repeat" => Some(Function::new_with_context(|context, argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+
+            let subject = arguments[0].as_string()?;
+            let count = as_usize(&arguments[1], "str::repeat")?;
+            let max_length = context.max_repeated_string_length();
+            match subject.len().checked_mul(count) {
+                Some(length) if length <= max_length => Ok(Value::from(subject.repeat(count))),
+                _ => Err(EvalexprError::CustomMessage(format!(
+                    "str::repeat result would exceed the maximum string length of {} bytes",
+                    max_length
+                ))),
+            }
+        })),
+        "str::pad_left" => str_pad(true),
+        "str::pad_right" => str_pad(false),
+        "str::parse_int" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            parse_int(&subject)
+        })),
+        "str::parse_float" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            parse_float(&subject)
+        })),
+        "str::format" => Some(Function::new(|argument| {
+            let (format, values) = match argument {
+                Value::Tuple(arguments) if !arguments.is_empty() => {
+                    (arguments[0].as_string()?, &arguments[1..])
+                },
+                argument => (argument.as_string()?, &[][..]),
+            };
+            format_string(&format, values).map(Value::String)
+        })),
         "str::from" => Some(Function::new(|argument| {
             Ok(Value::String(argument.to_string()))
         })),
         #[cfg(feature = "rand")]
         "random" => Some(Function::new(|argument| {
-            argument.as_empty()?;
-            Ok(Value::Float(rand::random()))
+            random(&mut rand::thread_rng(), argument)
+        })),
+        #[cfg(feature = "rand")]
+        "random_int" => Some(Function::new(|argument| {
+            random_int(&mut rand::thread_rng(), argument)
         })),
         // Bitwise operators
         "bitand" => int_function!(bitand, 2),
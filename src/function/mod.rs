@@ -1,13 +1,19 @@
 use std::fmt;
 
-use crate::{error::EvalexprResult, value::Value};
+use crate::{
+    error::{expect_function_argument_amount, EvalexprResult},
+    value::Value,
+    Context,
+};
 
 pub(crate) mod builtin;
+#[cfg(feature = "regex_support")]
+mod regex_cache;
 
 /// A helper trait to enable cloning through `Fn` trait objects.
 trait ClonableFn
 where
-    Self: Fn(&Value) -> EvalexprResult<Value>,
+    Self: Fn(&dyn Context, &Value) -> EvalexprResult<Value>,
     Self: Send + Sync + 'static,
 {
     fn dyn_clone(&self) -> Box<dyn ClonableFn>;
@@ -15,7 +21,7 @@ where
 
 impl<F> ClonableFn for F
 where
-    F: Fn(&Value) -> EvalexprResult<Value>,
+    F: Fn(&dyn Context, &Value) -> EvalexprResult<Value>,
     F: Send + Sync + 'static,
     F: Clone,
 {
@@ -59,14 +65,83 @@ impl Function {
         F: Fn(&Value) -> EvalexprResult<Value>,
         F: Send + Sync + 'static,
         F: Clone,
+    {
+        Self::new_with_context(move |_, argument| function(argument))
+    }
+
+    /// Creates a user-defined function that has access to the context it is evaluated with.
+    ///
+    /// The `function` receives the context it is evaluated with and the argument.
+    /// If the context that contains the function is wrapped, e.g. in a `ChainedContext`, then the function receives the wrapper.
+    /// The context is borrowed immutably for the duration of the call, so the function can read variables and call other functions of the context, but it cannot assign to variables.
+    /// This holds even when the function is evaluated with a mutable context, e.g. via `eval_with_context_mut`.
+    /// Any state that the function needs to mutate must therefore be kept in the function itself, e.g. behind a `Mutex`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// context.set_value("offset".into(), Value::from(10)).unwrap(); // Do proper error handling here
+    /// context.set_function("shift".into(), Function::new_with_context(|context, argument| {
+    ///     let offset = context.get_value("offset").cloned().unwrap_or(Value::from(0));
+    ///     Ok(Value::from(argument.as_int()? + offset.as_int()?))
+    /// })).unwrap(); // Do proper error handling here
+    /// assert_eq!(eval_with_context("shift(4)", &context), Ok(Value::from(14)));
+    /// ```
+    pub fn new_with_context<F>(function: F) -> Self
+    where
+        F: Fn(&dyn Context, &Value) -> EvalexprResult<Value>,
+        F: Send + Sync + 'static,
+        F: Clone,
     {
         Self {
             function: Box::new(function) as _,
         }
     }
 
-    pub(crate) fn call(&self, argument: &Value) -> EvalexprResult<Value> {
-        (self.function)(argument)
+    /// Creates a user-defined function that takes exactly `arity` arguments.
+    ///
+    /// The `function` receives the arguments as a slice, and is only called if the amount of arguments equals `arity`.
+    /// Otherwise, calling the function results in an `EvalexprError::WrongFunctionArgumentAmount`.
+    /// Multiple arguments are passed as a tuple, and no argument is passed as `Value::Empty`.
+    /// If `arity` is one, then the argument is passed as is, even if it is a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = HashMapContext::new();
+    /// context.set_function("add".into(), Function::new_fixed(2, |arguments| {
+    ///     Ok(Value::from(arguments[0].as_int()? + arguments[1].as_int()?))
+    /// })).unwrap(); // Do proper error handling here
+    /// assert_eq!(eval_with_context("add(4, 5)", &context), Ok(Value::from(9)));
+    /// assert_eq!(
+    ///     eval_with_context("add(4)", &context),
+    ///     Err(EvalexprError::WrongFunctionArgumentAmount { expected: 2, actual: 1 })
+    /// );
+    /// ```
+    pub fn new_fixed<F>(arity: usize, function: F) -> Self
+    where
+        F: Fn(&[Value]) -> EvalexprResult<Value>,
+        F: Send + Sync + 'static,
+        F: Clone,
+    {
+        Self::new(move |argument| {
+            let arguments = match argument {
+                Value::Empty => &[],
+                Value::Tuple(tuple) if arity != 1 => tuple.as_slice(),
+                argument => std::slice::from_ref(argument),
+            };
+            expect_function_argument_amount(arguments.len(), arity)?;
+            function(arguments)
+        })
+    }
+
+    pub(crate) fn call(&self, context: &dyn Context, argument: &Value) -> EvalexprResult<Value> {
+        (self.function)(context, argument)
     }
 }
 
@@ -78,6 +153,7 @@ impl fmt::Debug for Function {
 
 /// A trait to ensure a type is `Send` and `Sync`.
 /// If implemented for a type, the crate will not compile if the type is not `Send` and `Sync`.
+#[allow(dead_code)]
 trait IsSendAndSync: Send + Sync {}
 
 impl IsSendAndSync for Function {}
@@ -0,0 +1,82 @@
+use regex::Regex;
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::{EvalexprError, EvalexprResult};
+
+/// The maximum amount of compiled regexes kept per thread.
+/// When the cache is full, it is cleared before a new regex is inserted.
+const MAX_CACHED_REGEXES: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+#[cfg(test)]
+thread_local! {
+    static COMPILATION_COUNT: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
+
+/// Returns the compiled regex for the given pattern, reusing a previous compilation of the same pattern on this thread if possible.
+/// Cloning a `Regex` is cheap, as its internals are reference counted.
+pub(crate) fn cached_regex(re_str: &str) -> EvalexprResult<Regex> {
+    REGEX_CACHE.with(|cache| {
+        if let Some(re) = cache.borrow().get(re_str) {
+            return Ok(re.clone());
+        }
+
+        #[cfg(test)]
+        COMPILATION_COUNT.with(|count| count.set(count.get() + 1));
+        let re = Regex::new(re_str)
+            .map_err(|err| EvalexprError::invalid_regex(re_str.to_string(), format!("{}", err)))?;
+
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= MAX_CACHED_REGEXES {
+            cache.clear();
+        }
+        cache.insert(re_str.to_string(), re.clone());
+        Ok(re)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cached_regex, COMPILATION_COUNT, MAX_CACHED_REGEXES};
+    use crate::{eval, EvalexprError, Value};
+
+    fn compilation_count() -> usize {
+        COMPILATION_COUNT.with(|count| count.get())
+    }
+
+    #[test]
+    fn test_regex_cache_reuses_compilations() {
+        let initial_count = compilation_count();
+        for _ in 0..1000 {
+            assert_eq!(
+                eval("str::regex_matches(\"foobar\", \"[ob]{3}\")"),
+                Ok(Value::Boolean(true))
+            );
+            assert_eq!(
+                eval("str::regex_replace(\"foobar\", \"[ob]{3}\", \"x\")"),
+                Ok(Value::from("fxar"))
+            );
+        }
+        assert_eq!(compilation_count() - initial_count, 1);
+
+        // Invalid patterns are not cached and keep failing the same way
+        for _ in 0..2 {
+            match cached_regex("[") {
+                Err(EvalexprError::InvalidRegex { regex, .. }) => assert_eq!(regex, "["),
+                v => panic!("{:?}", v),
+            }
+        }
+        assert_eq!(compilation_count() - initial_count, 3);
+    }
+
+    #[test]
+    fn test_regex_cache_is_bounded() {
+        for i in 0..MAX_CACHED_REGEXES * 3 {
+            assert!(cached_regex(&format!("a{{{}}}", i)).is_ok());
+            assert!(super::REGEX_CACHE.with(|cache| cache.borrow().len()) <= MAX_CACHED_REGEXES);
+        }
+    }
+}
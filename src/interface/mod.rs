@@ -1,6 +1,7 @@
 use crate::{
     token, tree, value::TupleType, Context, ContextWithMutableVariables, EmptyType, EvalexprError,
-    EvalexprResult, FloatType, HashMapContext, IntType, Node, Value, EMPTY_VALUE,
+    EvalexprResult, FloatType, HashMapContext, IntType, Node, ParseError, Token, Value,
+    EMPTY_VALUE,
 };
 
 /// Evaluate the given expression string.
@@ -34,7 +35,35 @@ pub fn eval(string: &str) -> EvalexprResult<Value> {
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
 pub fn eval_with_context<C: Context>(string: &str, context: &C) -> EvalexprResult<Value> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)?.eval_with_context(context)
+    build_operator_tree(string)?.eval_with_context(context)
+}
+
+/// Evaluate the given expression string with the given context,
+/// failing with `EvalexprError::BudgetExceeded` if more than `budget` operators would need to be evaluated.
+///
+/// This protects the host from accidentally or maliciously expensive expressions.
+/// See `Node::eval_with_context_and_budget` for how operators are counted.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context = HashMapContext::new();
+/// assert_eq!(eval_with_context_and_budget("1 + 2", &context, 10), Ok(Value::from(3)));
+/// assert_eq!(
+///     eval_with_context_and_budget("1 + 2 + 3 + 4 + 5 + 6", &context, 10),
+///     Err(EvalexprError::BudgetExceeded { budget: 10 })
+/// );
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_with_context_and_budget<C: Context>(
+    string: &str,
+    context: &C,
+    budget: usize,
+) -> EvalexprResult<Value> {
+    build_operator_tree(string)?.eval_with_context_and_budget(context, budget)
 }
 
 /// Evaluate the given expression string with the given mutable context.
@@ -56,7 +85,7 @@ pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
     string: &str,
     context: &mut C,
 ) -> EvalexprResult<Value> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)?.eval_with_context_mut(context)
+    build_operator_tree(string)?.eval_with_context_mut(context)
 }
 
 /// Build the operator tree for the given expression string.
@@ -84,7 +113,76 @@ pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
 pub fn build_operator_tree(string: &str) -> EvalexprResult<Node> {
-    tree::tokens_to_operator_tree(token::tokenize(string)?)
+    Ok(build_operator_tree_with_position(string)?)
+}
+
+/// Build the operator tree for the given expression string, reporting the position of the error on failure.
+///
+/// Behaves like `build_operator_tree`, but a failure is returned as a `ParseError` that contains the byte position in the expression string at which the error was found.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let error = build_operator_tree_with_position("1 + (2 * 3").unwrap_err();
+/// assert_eq!(error.error(), &EvalexprError::UnmatchedLBrace);
+/// assert_eq!(error.position(), 4);
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn build_operator_tree_with_position(string: &str) -> Result<Node, ParseError> {
+    build_operator_tree_with_max_depth(string, usize::MAX)
+}
+
+/// Build the operator tree for the given expression string, failing if it is nested deeper than `max_depth`.
+///
+/// Operator trees are built and evaluated recursively, so a pathologically nested expression like `((((...))))` can overflow the stack.
+/// To evaluate untrusted expressions safely, limit their depth with this function.
+/// The depth is measured like `Node::depth` does for the resulting tree, i.e. as the amount of operators on the longest path from the whole expression to a single value, where each pair of parentheses also counts as an operator.
+/// If the depth exceeds `max_depth`, a `ParseError` containing an `EvalexprError::MaxDepthExceeded` is returned.
+/// Like with `build_operator_tree_with_position`, the error contains the position at which it was found.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(build_operator_tree_with_max_depth("(1 + 2) * 3", 4).unwrap().eval(), Ok(Value::from(9)));
+/// assert!(build_operator_tree_with_max_depth("(1 + 2) * 3", 3).is_err());
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn build_operator_tree_with_max_depth(
+    string: &str,
+    max_depth: usize,
+) -> Result<Node, ParseError> {
+    tree::tokens_to_operator_tree(
+        token::tokenize_with_positions(string)?,
+        string.len(),
+        max_depth,
+    )
+}
+
+/// Split the given expression string into its tokens, without building an operator tree.
+///
+/// Whitespace and comments are skipped.
+/// As no operator tree is built, this does not detect errors in the structure of the expression, like unmatched parentheses.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(
+///     tokenize("a + 2"),
+///     Ok(vec![Token::Identifier("a".to_string()), Token::Plus, Token::Int(2)])
+/// );
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn tokenize(string: &str) -> EvalexprResult<Vec<Token>> {
+    token::tokenize(string)
 }
 
 /// Evaluate the given expression string into a string.
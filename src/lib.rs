@@ -100,6 +100,19 @@
 //! assert_eq!(precompiled.eval_boolean_with_context(&context), Ok(false));
 //! ```
 //!
+//! The precompiled `Node` is tokenized and parsed only once, so evaluating it repeatedly is cheaper than calling `eval_with_context` with the same string again.
+//! It can also be created with `str::parse`:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let precompiled: Node = "x * x + 1".parse().unwrap(); // Do proper error handling here
+//! let results: Vec<_> = (1..=3)
+//!     .map(|x| precompiled.eval_int_with_context(&context_map! { "x" => x }.unwrap()))
+//!     .collect();
+//! assert_eq!(results, vec![Ok(2), Ok(5), Ok(10)]);
+//! ```
+//!
 //! ## Features
 //!
 //! ### Operators
@@ -108,17 +121,23 @@
 //! Operators have a precedence to determine their order of evaluation, where operators of higher precedence are evaluated first.
 //! The precedence should resemble that of most common programming languages, especially Rust.
 //! Variables and values have a precedence of 200, and function literals have 190.
+//! The precedence and associativity of each operator are also available in code via `Operator::precedence` and `Operator::is_left_to_right`.
 //!
 //! Supported binary operators:
 //!
 //! | Operator | Precedence | Description |
 //! |----------|------------|-------------|
+//! | [] | 180 | Indexing, see [The Indexing Operator](#the-indexing-operator) |
 //! | ^ | 120 | Exponentiation |
+//! | ** | 120 | Exponentiation, same as `^` |
 //! | * | 100 | Product |
 //! | / | 100 | Division (integer if both arguments are integers, otherwise float) |
+//! | // | 100 | Floor division (rounds towards negative infinity if both arguments are integers, otherwise the float quotient is truncated towards zero) |
 //! | % | 100 | Modulo (integer if both arguments are integers, otherwise float) |
 //! | + | 95 | Sum or String Concatenation |
 //! | - | 95 | Difference |
+//! | & | 90 | Bitwise and of integers |
+//! | &#124; | 85 | Bitwise or of integers |
 //! | < | 80 | Lower than |
 //! | \> | 80 | Greater than |
 //! | <= | 80 | Lower than or equal |
@@ -127,6 +146,7 @@
 //! | != | 80 | Not equal |
 //! | && | 75 | Logical and |
 //! | &#124;&#124; | 70 | Logical or |
+//! | ?? | 67 | Null-coalescing, the right argument if the left argument is empty, otherwise the left argument |
 //! | = | 50 | Assignment |
 //! | += | 50 | Sum-Assignment or String-Concatenation-Assignment |
 //! | -= | 50 | Difference-Assignment |
@@ -146,6 +166,12 @@
 //! | - | 110 | Negation |
 //! | ! | 110 | Logical not |
 //!
+//! Supported ternary operators:
+//!
+//! | Operator | Precedence | Description |
+//! |----------|------------|-------------|
+//! | ? : | 60 | Conditional |
+//!
 //! Operators that take numbers as arguments can either take integers or floating point numbers.
 //! If one of the arguments is a floating point number, all others are converted to floating point numbers as well, and the resulting value is a floating point number as well.
 //! Otherwise, the result is an integer.
@@ -160,6 +186,33 @@
 //! assert_eq!(eval("2^2"), Ok(Value::from(4.0)));
 //! ```
 //!
+//! Comparisons cannot be chained without parentheses.
+//! An expression like `1 < x < 10` would compare the boolean result of `1 < x` with `10`, which is rarely intended.
+//! It is therefore rejected with `EvalexprError::ChainedComparison`, and should be written as `1 < x && x < 10` instead.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(
+//!     eval("1 < 2 < 3"),
+//!     Err(EvalexprError::ChainedComparison)
+//! );
+//! assert_eq!(eval("1 < 2 && 2 < 3"), Ok(Value::from(true)));
+//! assert_eq!(eval("(1 < 2) == true"), Ok(Value::from(true)));
+//! ```
+//!
+//! The `+` operator concatenates two strings.
+//! Strings are not converted implicitly, so adding a string and a value of another type is an error.
+//! Use `str::from` to convert a value to a string explicitly:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("\"foo\" + \"bar\""), Ok(Value::from("foobar")));
+//! assert!(eval("\"foo\" + 1").is_err());
+//! assert_eq!(eval("\"foo\" + str::from(1)"), Ok(Value::from("foo1")));
+//! ```
+//!
 //! #### The Aggregation Operator
 //!
 //! The aggregation operator aggregates a set of values into a tuple.
@@ -189,6 +242,86 @@
 //! ])));
 //! ```
 //!
+//! A trailing comma after the last element is ignored.
+//! This allows writing tuples with a single element, while parentheses around a single value without a comma only group it:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("(1,)"), Ok(Value::from(vec![Value::from(1)])));
+//! assert_eq!(eval("(1)"), Ok(Value::from(1)));
+//! assert_eq!(eval("(1, 2,)"), Ok(Value::from(vec![Value::from(1), Value::from(2)])));
+//! ```
+//!
+//! #### The Indexing Operator
+//!
+//! The indexing operator `collection[index]` selects the element of a tuple or the character of a string at the given index, counting from zero.
+//! It works like the builtin functions `get` for tuples and `str::char_at` for strings, and fails if the index is negative or out of bounds.
+//! Indexing binds stronger than all other operators except function calls, so `f(x)[0]` indexes the result of the function call.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = context_map! {
+//!     "s" => "hello",
+//!     "t" => Value::from(vec![Value::from(1), Value::from(vec![Value::from(2), Value::from(3)])])
+//! }.unwrap(); // Do proper error handling here
+//!
+//! assert_eq!(eval_with_context("s[1]", &context), Ok(Value::from("e")));
+//! assert_eq!(eval_with_context("t[0] + t[1][1]", &context), Ok(Value::from(4)));
+//! assert_eq!(eval("(1, 2, 3)[2]"), Ok(Value::from(3)));
+//! assert!(eval_with_context("t[2]", &context).is_err());
+//! ```
+//!
+//! #### The Conditional Operator
+//!
+//! The conditional operator `condition ? a : b` evaluates to `a` if the condition is true, and to `b` otherwise.
+//! Unlike the `if` function, it only evaluates the branch that is taken, so the other branch may contain assignments or fail without affecting the result.
+//! The condition binds weaker than comparisons and logical operators, and conditionals can be chained in the last branch.
+//! A conditional in the first branch needs to be surrounded by parentheses.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let mut context = HashMapContext::new();
+//! assert_eq!(eval_with_context_mut("a = 3; b = 5; a > b ? a : b", &mut context), Ok(Value::from(5)));
+//! assert_eq!(eval_with_context_mut("a < 0 ? -1 : a == 0 ? 0 : 1", &mut context), Ok(Value::from(1)));
+//! assert_eq!(eval_with_context_mut("true ? 1 : (a = 4)", &mut context), Ok(Value::from(1)));
+//! assert_eq!(context.get_value("a"), Some(&Value::from(3)));
+//! ```
+//!
+//! #### The Null-Coalescing Operator
+//!
+//! The null-coalescing operator `a ?? b` evaluates to `b` if `a` is empty, and to `a` otherwise.
+//! Like the conditional operator, it only evaluates `b` if it is needed.
+//! This is useful to provide defaults for variables that may be empty.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = context_map! {
+//!     "nothing" => Value::Empty,
+//!     "something" => 3,
+//! }.unwrap(); // Do proper error handling here
+//!
+//! assert_eq!(eval_with_context("nothing ?? 5", &context), Ok(Value::from(5)));
+//! assert_eq!(eval_with_context("something ?? 5", &context), Ok(Value::from(3)));
+//! assert_eq!(eval_with_context("something ?? 1 / 0", &context), Ok(Value::from(3)));
+//! ```
+//!
+//! #### The Logical Operators
+//!
+//! The logical operators `&&` and `||` evaluate their right operand only if the left operand does not determine the result already.
+//! Hence, the right operand of `false && b` and `true || b` is neither evaluated nor type checked.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("false && undefined > 0"), Ok(Value::from(false)));
+//! assert_eq!(eval("true || 1 / 0"), Ok(Value::from(true)));
+//! assert_eq!(eval("true && undefined > 0"), Err(EvalexprError::VariableIdentifierNotFound("undefined".to_string())));
+//! ```
+//!
 //! #### The Assignment Operator
 //!
 //! This crate features the assignment operator, that allows expressions to store their result in a variable in the expression context.
@@ -221,6 +354,20 @@
 //! assert_eq!(eval_boolean("a = true; a &&= false; a"), Ok(false));
 //! ```
 //!
+//! By default, assignments evaluate to `Value::Empty`.
+//! Contexts can make them evaluate to the assigned value instead via `Context::assignments_return_value`, for example with `HashMapContext::set_assignments_return_value(true)`.
+//! Then assignments can be used within larger expressions, and assignments can be chained, as the assignment operators are right-associative.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let mut context = HashMapContext::new();
+//! context.set_assignments_return_value(true);
+//! assert_eq!(eval_with_context_mut("y = (x = 3) + 1", &mut context), Ok(Value::from(4)));
+//! assert_eq!(eval_with_context_mut("a = b = x *= 2", &mut context), Ok(Value::from(6)));
+//! assert_eq!(eval_int_with_context("a + b + x + y", &context), Ok(22));
+//! ```
+//!
 //! #### The Expression Chaining Operator
 //!
 //! The expression chaining operator works as one would expect from programming languages that use the semicolon to end statements, like `Rust`, `C` or `Java`.
@@ -234,6 +381,9 @@
 //! let mut context = HashMapContext::new();
 //! assert_eq!(eval("1;2;3;4;"), Ok(Value::Empty));
 //! assert_eq!(eval("1;2;3;4"), Ok(4.into()));
+//! // Assignments in earlier expressions persist in the context
+//! assert_eq!(eval_with_context_mut("a = 1; b = 2; a + b", &mut context), Ok(3.into()));
+//! assert_eq!(context.get_value("b"), Some(&2.into()));
 //!
 //! // Initialization of variables via script
 //! assert_eq!(eval_empty_with_context_mut("hp = 1; max_hp = 5; heal_amount = 3;", &mut context),
@@ -245,6 +395,74 @@
 //! assert_eq!(healing_script.eval_int_with_context_mut(&mut context), Ok(5));
 //! ```
 //!
+//! ### Comments
+//!
+//! A `#` outside of a string literal starts a comment that reaches until the end of the line.
+//! Comments are ignored like whitespace, which allows to document longer scripts.
+//! The `#` was chosen because `//` is the floor division operator.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let script = "
+//!     width = 2; # The width of a rectangle
+//!     height = 3;
+//!     area = width * height; # The result is stored in a variable
+//!     str::from(area) + \" # is not a comment in a string\"
+//! ";
+//! let mut context = HashMapContext::new();
+//! assert_eq!(eval_with_context_mut(script, &mut context), Ok(Value::from("6 # is not a comment in a string")));
+//! assert_eq!(context.get_value("area"), Some(&Value::from(6)));
+//! ```
+//!
+//! ### Parse Errors
+//!
+//! To find out where in an expression a parse error was detected, build the operator tree with `build_operator_tree_with_position`.
+//! It returns a `ParseError`, which contains the error and its byte position in the expression.
+//! This is the start of the token that caused the error, or the length of the expression if the error was only detected at its end.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let error = build_operator_tree_with_position("1 + (2 * 3").unwrap_err();
+//! assert_eq!(error.error(), &EvalexprError::UnmatchedLBrace);
+//! assert_eq!(error.position(), 4);
+//! assert_eq!(
+//!     build_operator_tree_with_position("(1 + 2)) * 3").map_err(|error| error.position()),
+//!     Err(7)
+//! );
+//! // The other functions return the error without its position
+//! assert_eq!(eval("1 + (2 * 3"), Err(EvalexprError::UnmatchedLBrace));
+//! ```
+//!
+//! Operator trees are built and evaluated recursively, so pathologically nested expressions can overflow the stack.
+//! When evaluating untrusted expressions, use `build_operator_tree_with_max_depth` to reject expressions that are nested too deeply.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let expression = "(".repeat(1000) + &")".repeat(1000);
+//! assert_eq!(
+//!     build_operator_tree_with_max_depth(&expression, 100).map_err(ParseError::into_error),
+//!     Err(EvalexprError::MaxDepthExceeded { max_depth: 100 })
+//! );
+//! assert!(build_operator_tree_with_max_depth("1 + 2 * 3", 100).is_ok());
+//! ```
+//!
+//! To also limit the time spent evaluating an untrusted expression, use `eval_with_context_and_budget`, which fails once more than the given amount of operators would need to be evaluated.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = HashMapContext::new();
+//! let expression = "1".to_string() + &" + 1".repeat(1000);
+//! assert_eq!(
+//!     eval_with_context_and_budget(&expression, &context, 100),
+//!     Err(EvalexprError::BudgetExceeded { budget: 100 })
+//! );
+//! assert_eq!(eval_with_context_and_budget("1 + 1", &context, 100), Ok(Value::from(2)));
+//! ```
+//!
 //! ### Contexts
 //!
 //! An expression evaluator that just evaluates expressions would be useful already, but this crate can do more.
@@ -283,6 +501,13 @@
 //! Type unsafe contexts may be implemented if requested.
 //! For reading `a`, it is enough to pass an immutable reference.
 //!
+//! To evaluate expressions that must not change a context, even when they are evaluated with a mutable reference, the context can be wrapped in a `ReadOnlyContext`.
+//! Assigning to a `ReadOnlyContext` yields `EvalexprError::ContextNotMutable`, while reading variables and calling functions works as with the wrapped context.
+//! To layer contexts, for example per-evaluation variables on top of shared defaults, a `ChainedContext` looks up identifiers in a primary context first and falls back to a second context, while assignments only go to the primary context.
+//! By default, reading a variable that is not set in the context is an error.
+//! To instead resolve missing variables to a value like `Value::Empty`, the context can be wrapped in a `DefaultContext`.
+//! Identifiers are case sensitive, unless the context is wrapped in a `CaseInsensitiveContext`, which converts all identifiers to lowercase.
+//!
 //! Contexts can also be manipulated in code.
 //! Take a look at the following example:
 //!
@@ -299,6 +524,22 @@
 //! // ...and read the value in code like this
 //! assert_eq!(context.get_value("a"), Some(&Value::from(10)));
 //! assert_eq!(context.get_value("b"), Some(&Value::from(1.0)));
+//! // ...or with a typed getter
+//! assert_eq!(context.get_float("b"), Ok(1.0));
+//! ```
+//!
+//! The variables stored in a context can be listed with the methods of the `IterateVariablesContext` trait, for example to take a snapshot of the state after evaluating assignments:
+//!
+//! ```rust
+//! use evalexpr::*;
+//! use std::collections::BTreeMap;
+//!
+//! let mut context = HashMapContext::new();
+//! eval_with_context_mut("a = 1; b = 2", &mut context).unwrap(); // Do proper error handling here
+//! let snapshot: BTreeMap<String, Value> = context.iter_variables().collect();
+//! assert_eq!(snapshot.len(), 2);
+//! assert_eq!(snapshot["a"], Value::from(1));
+//! assert_eq!(snapshot["b"], Value::from(2));
 //! ```
 //!
 //! Contexts are also required for user-defined functions.
@@ -315,6 +556,11 @@
 //!
 //! For more information about user-defined functions, refer to the respective [section](#user-defined-functions).
 //!
+//! By default, the integer operators `+`, `-` and `*` return an error if their result overflows.
+//! Contexts can change this behavior via `Context::integer_overflow_mode`.
+//! For example, with `HashMapContext::set_integer_overflow_mode(IntegerOverflowMode::Saturating)`, overflowing results are clamped to the range of `IntType` instead,
+//! and with `IntegerOverflowMode::Wrapping`, they wrap around like fixed-width machine integers.
+//!
 //! ### Builtin Functions
 //!
 //! This crate offers a set of builtin functions.
@@ -323,12 +569,35 @@
 //! |----------------------|-----------------|------------------------|-------------|
 //! | `min`                | >= 1            | Numeric                | Returns the minimum of the arguments |
 //! | `max`                | >= 1            | Numeric                | Returns the maximum of the arguments |
-//! | `len`                | 1               | String/Tuple           | Returns the character length of a string, or the amount of elements in a tuple (not recursively) |
+//! | `len`                | 1               | String/Tuple           | Returns the length of a string in bytes, or the amount of elements in a tuple (not recursively) |
+//! | `get`                | 2               | Tuple, Int             | Returns the element of the tuple at the given index |
+//! | `contains`           | 2               | Tuple, Any             | Returns true if the tuple contains an element equal to the second argument |
+//! | `slice`              | 3               | Tuple, Int, Int        | Returns the elements of the tuple from the start index given by the second argument (inclusive) to the end index given by the third argument (exclusive) |
+//! | `concat`             | >= 1            | Tuple                  | Returns a tuple containing the elements of all argument tuples in order |
+//! | `reverse`            | 1               | String/Tuple           | Returns the characters of a string, or the elements of a tuple, in reverse order |
+//! | `sort`               | >= 1            | Numeric/String         | Returns the elements of a tuple of only numbers or only strings in ascending order. Fails if one of the numbers is NaN |
+//! | `range`              | 2               | Int, Int               | Returns a tuple of the integers from the first argument (inclusive) to the second argument (exclusive) |
+//! | `map`                | 2               | Tuple, String          | Returns a tuple of the results of calling the function named by the second argument with each element of the tuple. The function is looked up in the context first and among the builtin functions second |
+//! | `filter`             | 2               | Tuple, String          | Returns a tuple of the elements of the tuple for which the function named by the second argument returns true. The function is looked up like for `map`, and must return a boolean |
+//! | `reduce`             | 3               | Tuple, String, Any     | Folds the tuple with the function named by the second argument, starting with the third argument. The function is looked up like for `map`, and is called with a tuple of the accumulated value and the next element |
 //! | `floor`              | 1               | Numeric                | Returns the largest integer less than or equal to a number |
 //! | `round`              | 1               | Numeric                | Returns the nearest integer to a number. Rounds half-way cases away from 0.0 |
 //! | `ceil`               | 1               | Numeric                | Returns the smallest integer greater than or equal to a number |
+//! | `math::trunc`        | 1               | Numeric                | Returns the integer part of a number, rounding towards zero |
+//! | `math::fract`        | 1               | Numeric                | Returns the fractional part of a number, having the same sign as the number |
+//! | `math::round_to`     | 2               | Numeric, Int           | Rounds a number to the given amount of decimal places, following `(number * 10^places).round() / 10^places`. A negative amount of places rounds to tens, hundreds, and so on. If `10^places` does not fit into a float, the number is returned unchanged, or as zero for negative places |
 //! | `if`                 | 3               | Boolean, Any, Any      | If the first argument is true, returns the second argument, otherwise, returns the third  |
+//! | `xor`                | 2               | Boolean, Boolean       | Returns true if exactly one of the arguments is true |
 //! | `typeof`             | 1               | Any                    | returns "string", "float", "int", "boolean", "tuple", or "empty" depending on the type of the argument  |
+//! | `is_string`          | 1               | Any                    | Returns true if the argument is a string |
+//! | `is_int`             | 1               | Any                    | Returns true if the argument is an integer |
+//! | `is_float`           | 1               | Any                    | Returns true if the argument is a float |
+//! | `is_number`          | 1               | Any                    | Returns true if the argument is an integer or a float |
+//! | `is_boolean`         | 1               | Any                    | Returns true if the argument is a boolean |
+//! | `is_tuple`           | 1               | Any                    | Returns true if the argument is a tuple. Multiple arguments are passed as a tuple |
+//! | `is_empty`           | 0 or 1          | Any                    | Returns true if the argument is empty, i.e. if no argument is given or the argument is `()` |
+//! | `to_int`             | 1               | Int/Float/String       | Converts the argument to an integer. Floats are truncated towards zero, and strings are parsed |
+//! | `to_float`           | 1               | Int/Float/String       | Converts the argument to a float. Strings are parsed |
 //! | `math::is_nan`       | 1               | Numeric                | Returns true if the argument is the floating-point value NaN, false if it is another floating-point value, and throws an error if it is not a number  |
 //! | `math::is_finite`    | 1               | Numeric                | Returns true if the argument is a finite floating-point number, false otherwise  |
 //! | `math::is_infinite`  | 1               | Numeric                | Returns true if the argument is an infinite floating-point number, false otherwise  |
@@ -340,6 +609,8 @@
 //! | `math::exp`          | 1               | Numeric                | Returns `e^(number)`, (the exponential function) |
 //! | `math::exp2`         | 1               | Numeric                | Returns `2^(number)` |
 //! | `math::pow`          | 2               | Numeric, Numeric       | Raises a number to the power of the other number |
+//! | `math::to_radians`   | 1               | Numeric                | Converts a number from degrees to radians |
+//! | `math::to_degrees`   | 1               | Numeric                | Converts a number from radians to degrees |
 //! | `math::cos`          | 1               | Numeric                | Computes the cosine of a number (in radians) |
 //! | `math::acos`         | 1               | Numeric                | Computes the arccosine of a number. The return value is in radians in the range [0, pi] or NaN if the number is outside the range [-1, 1] |
 //! | `math::cosh`         | 1               | Numeric                | Hyperbolic cosine function |
@@ -356,11 +627,38 @@
 //! | `math::sqrt`         | 1               | Numeric                | Returns the square root of a number. Returns NaN for a negative number |
 //! | `math::cbrt`         | 1               | Numeric                | Returns the cube root of a number |
 //! | `math::hypot`        | 2               | Numeric                | Calculates the length of the hypotenuse of a right-angle triangle given legs of length given by the two arguments |
+//! | `math::abs`          | 1               | Numeric                | Returns the absolute value of a number, returning an integer if the argument is an integer |
+//! | `math::signum`       | 1               | Numeric                | Returns the sign of a number as `-1`, `0` or `1` for integers, and as `-1.0`, `1.0` or NaN for floats, following `f64::signum` |
+//! | `math::clamp`        | 3               | Numeric                | Restricts the first argument to the range between the second and the third argument. Returns the first argument unchanged if it already lies in that range or is NaN, otherwise an integer if all arguments are integers and a float if not |
+//! | `math::gcd`          | 2               | Int, Int               | Returns the non-negative greatest common divisor of the integers |
+//! | `math::lcm`          | 2               | Int, Int               | Returns the non-negative least common multiple of the integers |
+//! | `math::sum`          | 1               | Tuple                  | Returns the sum of the numbers in the tuple, which is 0 for `()`. Returns an integer if all numbers are integers, otherwise a float |
+//! | `math::product`      | 1               | Tuple                  | Returns the product of the numbers in the tuple, which is 1 for `()`. Returns an integer if all numbers are integers, otherwise a float |
+//! | `math::mean`         | 1               | Tuple                  | Returns the arithmetic mean of the numbers in the non-empty tuple as a float |
+//! | `math::median`       | 1               | Tuple                  | Returns the median of the numbers in the non-empty tuple as a float, or an error if one of them is NaN. For an even amount of numbers, the mean of the two middle numbers is returned |
 //! | `str::regex_matches` | 2               | String, String         | Returns true if the first argument matches the regex in the second argument (Requires `regex_support` feature flag) |
 //! | `str::regex_replace` | 3               | String, String, String | Returns the first argument with all matches of the regex in the second argument replaced by the third argument (Requires `regex_support` feature flag) |
+//! | `str::regex_captures` | 2               | String, String         | Returns a tuple of the groups captured by the first match of the regex in the second argument in the first argument, starting with the whole match. Groups that did not participate in the match are empty. Returns an empty tuple if there is no match (Requires `regex_support` feature flag) |
+//! | `str::regex_split`   | 2               | String, String         | Returns a tuple of the substrings of the first argument separated by matches of the regex in the second argument (Requires `regex_support` feature flag) |
 //! | `str::to_lowercase`  | 1               | String                 | Returns the lower-case version of the string |
 //! | `str::to_uppercase`  | 1               | String                 | Returns the upper-case version of the string |
 //! | `str::trim`          | 1               | String                 | Strips whitespace from the start and the end of the string |
+//! | `str::char_count`    | 1               | String                 | Returns the amount of characters (Unicode scalar values) in the string |
+//! | `str::split`         | 2               | String, String         | Returns a tuple of the substrings of the first argument separated by the second argument |
+//! | `str::join`          | 2               | Tuple, String          | Returns the strings in the first argument joined by the second argument. Joining `()` yields the empty string |
+//! | `str::contains`      | 2               | String, String         | Returns true if the second argument is a substring of the first argument |
+//! | `str::starts_with`   | 2               | String, String         | Returns true if the first argument starts with the second argument |
+//! | `str::ends_with`     | 2               | String, String         | Returns true if the first argument ends with the second argument |
+//! | `str::index_of`      | 2               | String, String         | Returns the character index of the first occurrence of the second argument in the first argument, or -1 if it does not occur |
+//! | `str::replace`       | 3               | String, String, String | Returns the first argument with all occurrences of the second argument replaced by the third argument |
+//! | `str::substring`     | 3               | String, Int, Int       | Returns the substring of the first argument that starts at the character index given by the second argument and has the character length given by the third argument. Indices beyond the end of the string are clamped |
+//! | `str::char_at`       | 2               | String, Int            | Returns the character at the given character index of the string as a string |
+//! | `str::repeat`        | 2               | String, Int            | Returns the string repeated the given amount of times |
+//! | `str::pad_left`      | 3               | String, Int, String    | Returns the string padded at the start with the single character of the third argument, until it is at least as long as the given amount of characters |
+//! | `str::pad_right`     | 3               | String, Int, String    | Returns the string padded at the end with the single character of the third argument, until it is at least as long as the given amount of characters |
+//! | `str::format`        | >= 1            | String, Any...         | Returns the first argument with each `{}` replaced by the next of the remaining arguments. Strings are inserted without quotes, and `{{` and `}}` insert literal braces |
+//! | `str::parse_int`     | 1               | String                 | Parses the string as an integer |
+//! | `str::parse_float`   | 1               | String                 | Parses the string as a float |
 //! | `str::from`          | >= 0            | Any                    | Returns passed value as string |
 //! | `bitand`             | 2               | Int                    | Computes the bitwise and of the given integers |
 //! | `bitor`              | 2               | Int                    | Computes the bitwise or of the given integers |
@@ -368,13 +666,19 @@
 //! | `bitnot`             | 1               | Int                    | Computes the bitwise not of the given integer |
 //! | `shl`                | 2               | Int                    | Computes the given integer bitwise shifted left by the other given integer |
 //! | `shr`                | 2               | Int                    | Computes the given integer bitwise shifted right by the other given integer |
-//! | `random`             | 0               | Empty                  | Return a random float between 0 and 1. Requires the `rand` feature flag. |
+//! | `random`             | 0 or 2          | Empty or Numeric       | Return a random float between 0 and 1, or between the first and the second argument if given. Requires the `rand` feature flag. |
+//! | `random_int`         | 2               | Int, Int               | Return a random integer between the first and the second argument (both inclusive). Requires the `rand` feature flag. |
 //!
 //! The `min` and `max` functions can deal with a mixture of integer and floating point arguments.
+//! Integers are compared exactly, without converting them to floats.
 //! If the maximum or minimum is an integer, then an integer is returned.
 //! Otherwise, a float is returned.
 //!
 //! The regex functions require the feature flag `regex_support`.
+//! Compiled regexes are cached per thread, so evaluating the same pattern repeatedly does not recompile it.
+//!
+//! The random functions draw from a thread-local random number generator.
+//! For reproducible results, a `HashMapContext` can be seeded with `HashMapContext::set_random_seed`.
 //!
 //! ### Values
 //!
@@ -386,21 +690,33 @@
 //! |------------|---------|
 //! | `Value::String` | `"abc"`, `""`, `"a\"b\\c"` |
 //! | `Value::Boolean` | `true`, `false` |
-//! | `Value::Int` | `3`, `-9`, `0`, `135412` |
+//! | `Value::Int` | `3`, `-9`, `0`, `135412`, `0xFF`, `0o17`, `0b1010` |
 //! | `Value::Float` | `3.`, `.35`, `1.00`, `0.5`, `123.554`, `23e4`, `-2e-3`, `3.54e+2` |
 //! | `Value::Tuple` | `(3, 55.0, false, ())`, `(1, 2)` |
 //! | `Value::Empty` | `()` |
 //!
+//! Within string literals, the escape sequences `\"`, `\\`, `\n`, `\r`, `\t` and `\u{...}` are supported, where the braces contain the hexadecimal code point of a unicode character, like `\u{1F600}`.
+//! Other escape sequences are rejected with `EvalexprError::IllegalEscapeSequence`.
+//!
 //! Integers are internally represented as `i64`, and floating point numbers are represented as `f64`.
+//! Integers can be written in hexadecimal, octal or binary notation with the prefixes `0x`, `0o` and `0b`.
+//! Such literals must fit into an `i64`, otherwise parsing fails with `EvalexprError::IntLiteralOutOfRange`.
+//! Numbers can contain underscores to separate digits, like `1_000_000` or `3.141_592`.
+//! Each underscore needs to be placed between two digits, so literals like `1__0`, `1_` or `1_.5` fail to parse with `EvalexprError::MisplacedDigitSeparator`.
 //! Tuples are represented as `Vec<Value>` and empty values are not stored, but represented by Rust's unit type `()` where necessary.
 //!
 //! There exist type aliases for some of the types.
 //! They include `IntType`, `FloatType`, `TupleType` and `EmptyType`.
 //!
 //! Values can be constructed either directly or using the `From` trait.
-//! They can be decomposed using the `Value::as_[type]` methods.
+//! They can be decomposed using the `Value::as_[type]` methods, or, without cloning, using the consuming `Value::into_[type]` methods.
 //! The type of a value can be checked using the `Value::is_[type]` methods.
 //!
+//! The `Display` implementation of `Value` writes values in the notation of the table above.
+//! Strings are quoted and escaped, and floats always contain a decimal point or an exponent,
+//! such that evaluating the displayed string of a string, number, boolean or empty value results in the same value.
+//! Non-finite floats are an exception, as they have no notation in expressions.
+//!
 //! **Examples for constructing a value:**
 //!
 //! | Code | Result |
@@ -455,11 +771,36 @@
 //! The function gets passed what ever value is directly behind it, be it a tuple or a single values.
 //! If there is no value behind a function, it is interpreted as a variable instead.
 //! More specifically, a function needs to be followed by either an opening brace `(`, another literal, or a value.
-//! While not including special support for multi-valued functions, they can be realized by requiring a single tuple argument.
+//! Multi-valued functions can be realized by requiring a single tuple argument.
+//! For functions with a fixed amount of arguments, `Function::new_fixed` verifies the amount of arguments and passes them as a slice.
+//! Functions created with `Function::new_with_context` additionally receive the context they are evaluated with, which allows them to read its variables.
 //!
 //! Be aware that functions need to verify the types of values that are passed to them.
 //! The `error` module contains some shortcuts for verification, and error types for passing a wrong value type.
 //! Also, most numeric functions need to distinguish between being called with integers or floating point numbers, and act accordingly.
+//! To report domain-specific errors, functions can return `EvalexprError::custom`, whose message is passed through to the caller unchanged.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = context_map! {
+//!     "sqrt" => Function::new(|argument| {
+//!         let number = argument.as_number()?;
+//!         if number < 0.0 {
+//!             Err(EvalexprError::custom("argument must be positive"))
+//!         } else {
+//!             Ok(Value::Float(number.sqrt()))
+//!         }
+//!     })
+//! }.unwrap(); // Do proper error handling here
+//!
+//! assert_eq!(eval_with_context("sqrt 4", &context), Ok(Value::Float(2.0)));
+//! assert_eq!(
+//!     eval_with_context("sqrt(-4)", &context),
+//!     Err(EvalexprError::CustomMessage("argument must be positive".to_string()))
+//! );
+//! ```
+//!
 //!
 //! Here are some examples and counter-examples on expressions that are interpreted as function calls:
 //!
@@ -475,6 +816,11 @@
 //!
 //! Functions have a precedence of 190.
 //!
+//! ### Displaying Operator Trees
+//!
+//! The `Display` implementation of `Node` writes the operator tree as an expression in infix notation.
+//! Parsing this expression again results in an equal operator tree.
+//!
 //! ### [Serde](https://serde.rs)
 //!
 //! To use this crate with serde, the `serde_support` feature flag has to be set.
@@ -485,8 +831,8 @@
 //! evalexpr = {version = "7", features = ["serde_support"]}
 //! ```
 //!
-//! This crate implements `serde::de::Deserialize` for its type `Node` that represents a parsed expression tree.
-//! The implementation expects a [serde `string`](https://serde.rs/data-model.html) as input.
+//! This crate implements `serde::de::Deserialize` and `serde::ser::Serialize` for its type `Node` that represents a parsed expression tree.
+//! The implementation expects a [serde `string`](https://serde.rs/data-model.html) as input, and serializes a `Node` into the string given by its `Display` implementation.
 //! Example parsing with [ron format](docs.rs/ron):
 //!
 //! ```rust
@@ -514,6 +860,10 @@
 //! The crate also implements `Serialize` and `Deserialize` for the `HashMapContext`,
 //! but note that only the variables get (de)serialized, not the functions.
 //!
+//! `Value` implements `Serialize` and `Deserialize` as an enum, tagging each value with its variant.
+//! For formats like JSON, the module `untagged_value` provides a plain representation to be used with `#[serde(with = "evalexpr::untagged_value")]`,
+//! which maps numbers to numbers, strings to strings, tuples to arrays and the empty value to null.
+//!
 //! ## License
 //!
 //! This crate is primarily distributed under the terms of the MIT license.
@@ -535,18 +885,26 @@ extern crate serde_derive;
 
 pub use crate::{
     context::{
-        Context, ContextWithMutableFunctions, ContextWithMutableVariables, EmptyContext,
-        HashMapContext, IterateVariablesContext,
+        CaseInsensitiveContext, ChainedContext, Context, ContextWithMutableFunctions,
+        ContextWithMutableVariables, DefaultContext, EmptyContext, HashMapContext,
+        IntegerOverflowMode, IterateVariablesContext, ReadOnlyContext, DEFAULT_MAX_RANGE_LENGTH,
+        DEFAULT_MAX_REPEATED_STRING_LENGTH,
     },
-    error::{EvalexprError, EvalexprResult},
+    error::{EvalexprError, EvalexprResult, ParseError},
     function::Function,
     interface::*,
     operator::Operator,
-    token::PartialToken,
-    tree::Node,
-    value::{value_type::ValueType, EmptyType, FloatType, IntType, TupleType, Value, EMPTY_VALUE},
+    token::{PartialToken, Token},
+    tree::{Node, NodeIterMut},
+    value::{
+        value_type::ValueType, EmptyType, FloatType, HashableValue, IntType, TupleType, Value,
+        EMPTY_VALUE,
+    },
 };
 
+#[cfg(feature = "serde_support")]
+pub use crate::feature_serde::untagged_value;
+
 mod context;
 pub mod error;
 #[cfg(feature = "serde_support")]
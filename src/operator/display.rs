@@ -14,8 +14,10 @@ impl Display for Operator {
             Neg => write!(f, "-"),
             Mul => write!(f, "*"),
             Div => write!(f, "/"),
+            FloorDiv => write!(f, "//"),
             Mod => write!(f, "%"),
             Exp => write!(f, "^"),
+            Index => write!(f, "[]"),
 
             Eq => write!(f, "=="),
             Neq => write!(f, "!="),
@@ -27,18 +29,25 @@ impl Display for Operator {
             Or => write!(f, "||"),
             Not => write!(f, "!"),
 
-            Assign => write!(f, " = "),
-            AddAssign => write!(f, " += "),
-            SubAssign => write!(f, " -= "),
-            MulAssign => write!(f, " *= "),
-            DivAssign => write!(f, " /= "),
-            ModAssign => write!(f, " %= "),
-            ExpAssign => write!(f, " ^= "),
-            AndAssign => write!(f, " &&= "),
-            OrAssign => write!(f, " ||= "),
-
-            Tuple => write!(f, ", "),
-            Chain => write!(f, "; "),
+            BitAnd => write!(f, "&"),
+            BitOr => write!(f, "|"),
+
+            Conditional => write!(f, "?"),
+            ConditionalElse => write!(f, ":"),
+            Coalesce => write!(f, "??"),
+
+            Assign => write!(f, "="),
+            AddAssign => write!(f, "+="),
+            SubAssign => write!(f, "-="),
+            MulAssign => write!(f, "*="),
+            DivAssign => write!(f, "/="),
+            ModAssign => write!(f, "%="),
+            ExpAssign => write!(f, "^="),
+            AndAssign => write!(f, "&&="),
+            OrAssign => write!(f, "||="),
+
+            Tuple => write!(f, ","),
+            Chain => write!(f, ";"),
 
             Const { value } => write!(f, "{}", value),
             VariableIdentifierWrite { identifier } | VariableIdentifierRead { identifier } => {
@@ -1,6 +1,11 @@
-use crate::function::builtin::builtin_function;
-
-use crate::{context::Context, error::*, value::Value, ContextWithMutableVariables};
+use crate::function::builtin::{call_function_by_name, index};
+
+use crate::{
+    context::{Context, IntegerOverflowMode},
+    error::*,
+    value::Value,
+    ContextWithMutableVariables,
+};
 use std::borrow::Borrow;
 
 mod display;
@@ -22,10 +27,14 @@ pub enum Operator {
     Mul,
     /// A binary division operator.
     Div,
+    /// A binary floor division operator.
+    FloorDiv,
     /// A binary modulo operator.
     Mod,
     /// A binary exponentiation operator.
     Exp,
+    /// A binary indexing operator `collection[index]`, selecting an element of a tuple or a character of a string.
+    Index,
 
     /// A binary equality comparator.
     Eq,
@@ -46,6 +55,21 @@ pub enum Operator {
     /// A binary logical not operator.
     Not,
 
+    /// A binary bitwise and operator.
+    BitAnd,
+    /// A binary bitwise or operator.
+    BitOr,
+
+    /// The condition and the value if true of a ternary conditional `condition ? a : b`.
+    /// It is only valid as the first argument of a `ConditionalElse` operator.
+    Conditional,
+    /// A ternary conditional `condition ? a : b`, whose first argument is a `Conditional` operator.
+    /// Only the branch that is taken is evaluated.
+    ConditionalElse,
+    /// A binary null-coalescing operator, evaluating to its second argument if its first argument is empty.
+    /// The second argument is only evaluated if the first argument is empty.
+    Coalesce,
+
     /// A binary assignment operator.
     Assign,
     /// A binary add-assign operator.
@@ -110,22 +134,41 @@ impl Operator {
     }
 
     /// Returns the precedence of the operator.
-    /// A high precedence means that the operator has priority to be deeper in the tree.
-    pub(crate) const fn precedence(&self) -> i32 {
+    /// A high precedence means that the operator has priority to be deeper in the tree, i.e. it binds stronger.
+    ///
+    /// This is the data the parser uses, so tools like formatters can rely on it to stay in sync with the grammar.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// assert!(Operator::Mul.precedence() > Operator::Add.precedence());
+    /// assert!(Operator::Exp.precedence() > Operator::Mul.precedence());
+    /// ```
+    pub const fn precedence(&self) -> i32 {
         use crate::operator::Operator::*;
         match self {
             RootNode => 200,
 
             Add | Sub => 95,
             Neg => 110,
-            Mul | Div | Mod => 100,
+            Mul | Div | FloorDiv | Mod => 100,
             Exp => 120,
+            Index => 180,
 
             Eq | Neq | Gt | Lt | Geq | Leq => 80,
             And => 75,
             Or => 70,
             Not => 110,
 
+            BitAnd => 90,
+            BitOr => 85,
+
+            Coalesce => 67,
+            Conditional => 65,
+            ConditionalElse => 60,
+
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => 50,
 
@@ -138,12 +181,32 @@ impl Operator {
         }
     }
 
+    /// Returns true if this operator is a binary comparison.
+    pub(crate) const fn is_comparison(&self) -> bool {
+        use crate::operator::Operator::*;
+        matches!(self, Eq | Neq | Gt | Lt | Geq | Leq)
+    }
+
     /// Returns true if chains of operators with the same precedence as this one should be evaluated left-to-right,
     /// and false if they should be evaluated right-to-left.
     /// Left-to-right chaining has priority if operators with different order but same precedence are chained.
-    pub(crate) const fn is_left_to_right(&self) -> bool {
+    pub const fn is_left_to_right(&self) -> bool {
         use crate::operator::Operator::*;
-        !matches!(self, Assign | FunctionIdentifier { .. })
+        !matches!(
+            self,
+            Assign
+                | AddAssign
+                | SubAssign
+                | MulAssign
+                | DivAssign
+                | ModAssign
+                | ExpAssign
+                | AndAssign
+                | OrAssign
+                | Conditional
+                | ConditionalElse
+                | FunctionIdentifier { .. }
+        )
     }
 
     /// Returns true if chains of this operator should be flattened into one operator with many arguments.
@@ -162,9 +225,10 @@ impl Operator {
     pub(crate) const fn max_argument_amount(&self) -> Option<usize> {
         use crate::operator::Operator::*;
         match self {
-            Add | Sub | Mul | Div | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | And | Or
-            | Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
-            | AndAssign | OrAssign => Some(2),
+            Add | Sub | Mul | Div | FloorDiv | Mod | Exp | Index | Eq | Neq | Gt | Lt | Geq
+            | Leq | And | Or | BitAnd | BitOr | Assign | AddAssign | SubAssign | MulAssign
+            | DivAssign | ModAssign | ExpAssign | AndAssign | OrAssign | Conditional
+            | ConditionalElse | Coalesce => Some(2),
             Tuple | Chain => None,
             Not | Neg | RootNode => Some(1),
             Const { .. } => Some(0),
@@ -199,7 +263,11 @@ impl Operator {
                     result.push_str(&b);
                     Ok(Value::String(result))
                 } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    let result = a.checked_add(b);
+                    let result = match context.integer_overflow_mode() {
+                        IntegerOverflowMode::Checked => a.checked_add(b),
+                        IntegerOverflowMode::Saturating => Some(a.saturating_add(b)),
+                        IntegerOverflowMode::Wrapping => Some(a.wrapping_add(b)),
+                    };
                     if let Some(result) = result {
                         Ok(Value::Int(result))
                     } else {
@@ -224,7 +292,11 @@ impl Operator {
                 arguments[1].as_number()?;
 
                 if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    let result = a.checked_sub(b);
+                    let result = match context.integer_overflow_mode() {
+                        IntegerOverflowMode::Checked => a.checked_sub(b),
+                        IntegerOverflowMode::Saturating => Some(a.saturating_sub(b)),
+                        IntegerOverflowMode::Wrapping => Some(a.wrapping_sub(b)),
+                    };
                     if let Some(result) = result {
                         Ok(Value::Int(result))
                     } else {
@@ -260,7 +332,11 @@ impl Operator {
                 arguments[1].as_number()?;
 
                 if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    let result = a.checked_mul(b);
+                    let result = match context.integer_overflow_mode() {
+                        IntegerOverflowMode::Checked => a.checked_mul(b),
+                        IntegerOverflowMode::Saturating => Some(a.saturating_mul(b)),
+                        IntegerOverflowMode::Wrapping => Some(a.wrapping_mul(b)),
+                    };
                     if let Some(result) = result {
                         Ok(Value::Int(result))
                     } else {
@@ -296,6 +372,34 @@ impl Operator {
                     ))
                 }
             },
+            FloorDiv => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                arguments[0].as_number()?;
+                arguments[1].as_number()?;
+
+                if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
+                    // Round towards negative infinity if the division has a remainder
+                    let result = a.checked_div(b).map(|quotient| {
+                        if a % b != 0 && (a < 0) != (b < 0) {
+                            quotient - 1
+                        } else {
+                            quotient
+                        }
+                    });
+                    if let Some(result) = result {
+                        Ok(Value::Int(result))
+                    } else {
+                        Err(EvalexprError::division_error(
+                            arguments[0].clone(),
+                            arguments[1].clone(),
+                        ))
+                    }
+                } else {
+                    Ok(Value::Float(
+                        (arguments[0].as_number()? / arguments[1].as_number()?).trunc(),
+                    ))
+                }
+            },
             Mod => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
                 arguments[0].as_number()?;
@@ -326,6 +430,11 @@ impl Operator {
                     arguments[0].as_number()?.powf(arguments[1].as_number()?),
                 ))
             },
+            Index => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+
+                index(&arguments[0], &arguments[1])
+            },
             Eq => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
 
@@ -416,6 +525,31 @@ impl Operator {
 
                 Ok(Value::Boolean(!a))
             },
+            BitAnd => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a & b))
+            },
+            BitOr => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a | b))
+            },
+            Coalesce => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+
+                if arguments[0].is_empty() {
+                    Ok(arguments[1].clone())
+                } else {
+                    Ok(arguments[0].clone())
+                }
+            },
+            // Conditionals are evaluated lazily by their node, so reaching this means they are malformed
+            Conditional | ConditionalElse => Err(EvalexprError::UnmatchedConditional),
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => Err(EvalexprError::ContextNotMutable),
             Tuple => Ok(Value::Tuple(arguments.into())),
@@ -451,18 +585,7 @@ impl Operator {
                 expect_operator_argument_amount(arguments.len(), 1)?;
                 let arguments = &arguments[0];
 
-                match context.call_function(identifier, arguments) {
-                    Err(EvalexprError::FunctionIdentifierNotFound(_)) => {
-                        if let Some(builtin_function) = builtin_function(identifier) {
-                            builtin_function.call(arguments)
-                        } else {
-                            Err(EvalexprError::FunctionIdentifierNotFound(
-                                identifier.clone(),
-                            ))
-                        }
-                    },
-                    result => result,
-                }
+                call_function_by_name(context, identifier, arguments)
             },
         }
     }
@@ -480,7 +603,11 @@ impl Operator {
                 let target = arguments[0].as_string()?;
                 context.set_value(target, arguments[1].clone())?;
 
-                Ok(Value::Empty)
+                if context.assignments_return_value() {
+                    Ok(arguments[1].clone())
+                } else {
+                    Ok(Value::Empty)
+                }
             },
             AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign | AndAssign
             | OrAssign => {
@@ -507,9 +634,14 @@ impl Operator {
                         self
                     ),
                 }?;
-                context.set_value(target, result)?;
 
-                Ok(Value::Empty)
+                if context.assignments_return_value() {
+                    context.set_value(target, result.clone())?;
+                    Ok(result)
+                } else {
+                    context.set_value(target, result)?;
+                    Ok(Value::Empty)
+                }
             },
             _ => self.eval(arguments, context),
         }
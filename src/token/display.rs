@@ -9,7 +9,9 @@ impl fmt::Display for Token {
             Plus => write!(f, "+"),
             Minus => write!(f, "-"),
             Star => write!(f, "*"),
+            DoubleStar => write!(f, "**"),
             Slash => write!(f, "/"),
+            DoubleSlash => write!(f, "//"),
             Percent => write!(f, "%"),
             Hat => write!(f, "^"),
 
@@ -24,9 +26,15 @@ impl fmt::Display for Token {
             Or => write!(f, "||"),
             Not => write!(f, "!"),
 
+            // Bitwise
+            BitAnd => write!(f, "&"),
+            BitOr => write!(f, "|"),
+
             // Precedence
             LBrace => write!(f, "("),
             RBrace => write!(f, ")"),
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
 
             // Assignment
             Assign => write!(f, "="),
@@ -39,6 +47,11 @@ impl fmt::Display for Token {
             AndAssign => write!(f, "&&="),
             OrAssign => write!(f, "||="),
 
+            // Conditional
+            QuestionMark => write!(f, "?"),
+            Colon => write!(f, ":"),
+            Coalesce => write!(f, "??"),
+
             // Special
             Comma => write!(f, ","),
             Semicolon => write!(f, ";"),
@@ -72,6 +85,7 @@ impl fmt::Display for PartialToken {
             Lt => write!(f, "<"),
             Ampersand => write!(f, "&"),
             VerticalBar => write!(f, "|"),
+            QuestionMark => write!(f, "?"),
         }
     }
 }
@@ -1,55 +1,115 @@
 use crate::{
-    error::{EvalexprError, EvalexprResult},
+    error::{EvalexprError, EvalexprResult, ParseError},
     value::{FloatType, IntType},
 };
+use std::str::FromStr;
 
 mod display;
 
+/// A token is a lexical unit of an expression, such as an operator, a parenthesis, a literal value or an identifier.
+///
+/// Tokens are produced by [`tokenize`](crate::tokenize), which skips whitespace and comments.
+/// The `Display` implementation writes a token as it appears in an expression.
 #[derive(Clone, PartialEq, Debug)]
 pub enum Token {
     // Arithmetic
+    /// A plus sign '+'.
     Plus,
+    /// A minus sign '-'.
     Minus,
+    /// A star '*'.
     Star,
+    /// A double star '**'.
+    DoubleStar,
+    /// A slash '/'.
     Slash,
+    /// A double slash '//'.
+    DoubleSlash,
+    /// A percent sign '%'.
     Percent,
+    /// A hat '^'.
     Hat,
 
     // Logic
+    /// An equality comparator '=='.
     Eq,
+    /// An inequality comparator '!='.
     Neq,
+    /// A greater-than comparator '>'.
     Gt,
+    /// A lower-than comparator '<'.
     Lt,
+    /// A greater-than-or-equal comparator '>='.
     Geq,
+    /// A lower-than-or-equal comparator '<='.
     Leq,
+    /// A logical and '&&'.
     And,
+    /// A logical or '||'.
     Or,
+    /// A logical not '!'.
     Not,
 
+    // Bitwise
+    /// A bitwise and '&'.
+    BitAnd,
+    /// A bitwise or '|'.
+    BitOr,
+
     // Precedence
+    /// An opening parenthesis '('.
     LBrace,
+    /// A closing parenthesis ')'.
     RBrace,
+    /// An opening bracket '['.
+    LBracket,
+    /// A closing bracket ']'.
+    RBracket,
 
     // Assignment
+    /// An assignment '='.
     Assign,
+    /// An add-assignment '+='.
     PlusAssign,
+    /// A subtract-assignment '-='.
     MinusAssign,
+    /// A multiply-assignment '*='.
     StarAssign,
+    /// A divide-assignment '/='.
     SlashAssign,
+    /// A modulo-assignment '%='.
     PercentAssign,
+    /// An exponentiate-assignment '^='.
     HatAssign,
+    /// An and-assignment '&&='.
     AndAssign,
+    /// An or-assignment '||='.
     OrAssign,
 
+    // Conditional
+    /// A question mark '?'.
+    QuestionMark,
+    /// A colon ':'.
+    Colon,
+    /// A null-coalescing operator '??'.
+    Coalesce,
+
     // Special
+    /// A comma ','.
     Comma,
+    /// A semicolon ';'.
     Semicolon,
 
     // Values, Variables and Functions
+    /// An identifier of a variable or a function.
     Identifier(String),
+    /// A float literal.
     Float(FloatType),
+    /// An integer literal.
     Int(IntType),
+    /// A boolean literal.
     Boolean(bool),
+    /// A string literal, with its escape sequences resolved.
     String(String),
 }
 
@@ -86,6 +146,8 @@ pub enum PartialToken {
     Ampersand,
     /// A vertical bar character '|'.
     VerticalBar,
+    /// A question mark character '?'.
+    QuestionMark,
 }
 
 // Make this a const fn as soon as is_whitespace and to_string get stable (issue #57563)
@@ -100,6 +162,11 @@ fn char_to_partial_token(c: char) -> PartialToken {
 
         '(' => PartialToken::Token(Token::LBrace),
         ')' => PartialToken::Token(Token::RBrace),
+        '[' => PartialToken::Token(Token::LBracket),
+        ']' => PartialToken::Token(Token::RBracket),
+
+        '?' => PartialToken::QuestionMark,
+        ':' => PartialToken::Token(Token::Colon),
 
         ',' => PartialToken::Token(Token::Comma),
         ';' => PartialToken::Token(Token::Semicolon),
@@ -128,7 +195,9 @@ impl Token {
             Token::Plus => false,
             Token::Minus => false,
             Token::Star => false,
+            Token::DoubleStar => false,
             Token::Slash => false,
+            Token::DoubleSlash => false,
             Token::Percent => false,
             Token::Hat => false,
 
@@ -142,8 +211,17 @@ impl Token {
             Token::Or => false,
             Token::Not => false,
 
+            Token::BitAnd => false,
+            Token::BitOr => false,
+
             Token::LBrace => true,
             Token::RBrace => false,
+            Token::LBracket => false,
+            Token::RBracket => false,
+
+            Token::QuestionMark => false,
+            Token::Colon => false,
+            Token::Coalesce => false,
 
             Token::Comma => false,
             Token::Semicolon => false,
@@ -172,7 +250,9 @@ impl Token {
             Token::Plus => false,
             Token::Minus => false,
             Token::Star => false,
+            Token::DoubleStar => false,
             Token::Slash => false,
+            Token::DoubleSlash => false,
             Token::Percent => false,
             Token::Hat => false,
 
@@ -186,8 +266,17 @@ impl Token {
             Token::Or => false,
             Token::Not => false,
 
+            Token::BitAnd => false,
+            Token::BitOr => false,
+
             Token::LBrace => false,
             Token::RBrace => true,
+            Token::LBracket => false,
+            Token::RBracket => true,
+
+            Token::QuestionMark => false,
+            Token::Colon => false,
+            Token::Coalesce => false,
 
             Token::Comma => false,
             Token::Semicolon => false,
@@ -233,17 +322,55 @@ fn parse_escape_sequence<Iter: Iterator<Item = char>>(iter: &mut Iter) -> Evalex
     match iter.next() {
         Some('"') => Ok('"'),
         Some('\\') => Ok('\\'),
+        Some('n') => Ok('\n'),
+        Some('r') => Ok('\r'),
+        Some('t') => Ok('\t'),
+        Some('u') => parse_unicode_escape_sequence(iter),
         Some(c) => Err(EvalexprError::IllegalEscapeSequence(format!("\\{}", c))),
         None => Err(EvalexprError::IllegalEscapeSequence("\\".to_string())),
     }
 }
 
+/// Parses the part of a unicode escape sequence `\u{...}` after the `u`.
+/// The braces contain the hexadecimal code point of the character, consisting of one to six digits.
+fn parse_unicode_escape_sequence<Iter: Iterator<Item = char>>(
+    iter: &mut Iter,
+) -> EvalexprResult<char> {
+    let mut sequence = "\\u".to_string();
+    if iter.next() != Some('{') {
+        return Err(EvalexprError::IllegalEscapeSequence(sequence));
+    }
+    sequence.push('{');
+
+    let mut digits = String::new();
+    // At most six digits and the closing brace
+    for c in iter.take(7) {
+        sequence.push(c);
+        if c == '}' {
+            break;
+        }
+        digits.push(c);
+    }
+
+    if !sequence.ends_with('}')
+        || digits.is_empty()
+        || !digits.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(EvalexprError::IllegalEscapeSequence(sequence));
+    }
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(std::char::from_u32)
+        .ok_or(EvalexprError::IllegalEscapeSequence(sequence))
+}
+
 /// Parses a string value from the given character iterator.
 ///
 /// The first character from the iterator is interpreted as first character of the string.
 /// The string is terminated by a double quote `"`.
 /// Occurrences of `"` within the string can be escaped with `\`.
 /// The backslash needs to be escaped with another backslash `\`.
+/// Further escape sequences are `\n`, `\r`, `\t` and `\u{...}`.
 fn parse_string_literal<Iter: Iterator<Item = char>>(
     mut iter: &mut Iter,
 ) -> EvalexprResult<PartialToken> {
@@ -260,19 +387,101 @@ fn parse_string_literal<Iter: Iterator<Item = char>>(
     Ok(PartialToken::Token(Token::String(result)))
 }
 
-/// Converts a string to a vector of partial tokens.
-fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
+/// Removes the underscores from a numeric literal, if each of them separates two digits of the given radix like in `1_000`.
+///
+/// Returns `None` if an underscore is at the start or end of the literal, next to another underscore, or next to a non-digit.
+fn remove_digit_separators(literal: &str, radix: u32) -> Option<String> {
+    let chars: Vec<char> = literal.chars().collect();
+    for (index, c) in chars.iter().enumerate() {
+        if *c == '_' {
+            let is_digit = |index: Option<usize>| {
+                index
+                    .and_then(|index| chars.get(index))
+                    .map_or(false, |c| c.is_digit(radix))
+            };
+            if !is_digit(index.checked_sub(1)) || !is_digit(Some(index + 1)) {
+                return None;
+            }
+        }
+    }
+    Some(literal.replace('_', ""))
+}
+
+/// Returns true if the literal starts with a digit and would be a number without its underscores,
+/// i.e. if it is a number literal with underscores that do not separate two digits, like `1__0` or `5_`.
+fn has_misplaced_digit_separator(literal: &str) -> bool {
+    if !literal.starts_with(|c: char| c.is_ascii_digit()) || !literal.contains('_') {
+        return false;
+    }
+
+    let literal = literal.replace('_', "");
+    !matches!(parse_radix_int_literal(&literal), Ok(None)) || literal.parse::<FloatType>().is_ok()
+}
+
+/// Parses a decimal number literal that may contain underscores as digit separators.
+fn parse_decimal_literal<T: FromStr>(literal: &str) -> Option<T> {
+    remove_digit_separators(literal, 10)?.parse().ok()
+}
+
+/// Parses an integer literal with a `0x` (hexadecimal), `0o` (octal) or `0b` (binary) prefix.
+///
+/// Returns `None` if the literal does not consist of one of these prefixes followed by digits of the respective radix.
+fn parse_radix_int_literal(literal: &str) -> EvalexprResult<Option<IntType>> {
+    let (digits, radix) = if let Some(digits) = literal.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = literal.strip_prefix("0o") {
+        (digits, 8)
+    } else if let Some(digits) = literal.strip_prefix("0b") {
+        (digits, 2)
+    } else {
+        return Ok(None);
+    };
+
+    let digits = if let Some(digits) = remove_digit_separators(digits, radix) {
+        digits
+    } else {
+        return Ok(None);
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Ok(None);
+    }
+
+    // The digits are valid, so parsing can only fail if the number is too large
+    IntType::from_str_radix(&digits, radix)
+        .map(Some)
+        .map_err(|_| EvalexprError::IntLiteralOutOfRange(literal.to_string()))
+}
+
+/// Converts a string to a vector of partial tokens, each paired with the byte position where it starts.
+fn str_to_partial_tokens(string: &str) -> Result<Vec<(PartialToken, usize)>, ParseError> {
     let mut result = Vec::new();
-    let mut iter = string.chars().peekable();
+    let mut iter = string.char_indices().peekable();
 
-    while let Some(c) = iter.next() {
+    while let Some((position, c)) = iter.next() {
         if c == '"' {
-            result.push(parse_string_literal(&mut iter)?);
+            let mut chars = (&mut iter).map(|(_, c)| c);
+            let string_literal = parse_string_literal(&mut chars)
+                .map_err(|error| ParseError::new(error, position))?;
+            result.push((string_literal, position));
+        } else if c == '#' {
+            // A comment reaches until the end of the line and acts like whitespace
+            for (_, c) in &mut iter {
+                if c == '\n' {
+                    break;
+                }
+            }
+            result.push((PartialToken::Whitespace, position));
         } else {
-            let partial_token = char_to_partial_token(c);
+            let partial_token = if c == ':' && iter.peek().map(|(_, c)| *c) == Some(':') {
+                // A double colon is part of a namespaced identifier like `str::from`
+                iter.next();
+                PartialToken::Literal("::".to_string())
+            } else {
+                char_to_partial_token(c)
+            };
 
             let if_let_successful =
-                if let (Some(PartialToken::Literal(last)), PartialToken::Literal(literal)) =
+                if let (Some((PartialToken::Literal(last), _)), PartialToken::Literal(literal)) =
                     (result.last_mut(), &partial_token)
                 {
                     last.push_str(literal);
@@ -282,7 +491,7 @@ fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
                 };
 
             if !if_let_successful {
-                result.push(partial_token);
+                result.push((partial_token, position));
             }
         }
     }
@@ -290,158 +499,192 @@ fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
 }
 
 /// Resolves all partial tokens by converting them to complex tokens.
-fn partial_tokens_to_tokens(mut tokens: &[PartialToken]) -> EvalexprResult<Vec<Token>> {
+fn partial_tokens_to_tokens(
+    mut tokens: &[(PartialToken, usize)],
+) -> Result<Vec<(Token, usize)>, ParseError> {
     let mut result = Vec::new();
-    while !tokens.is_empty() {
-        let first = tokens[0].clone();
-        let second = tokens.get(1).cloned();
-        let third = tokens.get(2).cloned();
+    while let Some((first, position)) = tokens.first().cloned() {
+        let second = tokens.get(1).map(|(token, _)| token.clone());
+        let third = tokens.get(2).map(|(token, _)| token.clone());
         let mut cutoff = 2;
 
-        result.extend(
-            match first {
-                PartialToken::Token(token) => {
+        let token = match first {
+            PartialToken::Token(token) => {
+                cutoff = 1;
+                Some(token)
+            },
+            PartialToken::Plus => match second {
+                Some(PartialToken::Eq) => Some(Token::PlusAssign),
+                _ => {
                     cutoff = 1;
-                    Some(token)
+                    Some(Token::Plus)
                 },
-                PartialToken::Plus => match second {
-                    Some(PartialToken::Eq) => Some(Token::PlusAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Plus)
-                    },
+            },
+            PartialToken::Minus => match second {
+                Some(PartialToken::Eq) => Some(Token::MinusAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Minus)
                 },
-                PartialToken::Minus => match second {
-                    Some(PartialToken::Eq) => Some(Token::MinusAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Minus)
-                    },
+            },
+            PartialToken::Star => match second {
+                Some(PartialToken::Eq) => Some(Token::StarAssign),
+                Some(PartialToken::Star) => Some(Token::DoubleStar),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Star)
                 },
-                PartialToken::Star => match second {
-                    Some(PartialToken::Eq) => Some(Token::StarAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Star)
-                    },
+            },
+            PartialToken::Slash => match second {
+                Some(PartialToken::Eq) => Some(Token::SlashAssign),
+                Some(PartialToken::Slash) => Some(Token::DoubleSlash),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Slash)
                 },
-                PartialToken::Slash => match second {
-                    Some(PartialToken::Eq) => Some(Token::SlashAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Slash)
-                    },
+            },
+            PartialToken::Percent => match second {
+                Some(PartialToken::Eq) => Some(Token::PercentAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Percent)
                 },
-                PartialToken::Percent => match second {
-                    Some(PartialToken::Eq) => Some(Token::PercentAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Percent)
-                    },
+            },
+            PartialToken::Hat => match second {
+                Some(PartialToken::Eq) => Some(Token::HatAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Hat)
                 },
-                PartialToken::Hat => match second {
-                    Some(PartialToken::Eq) => Some(Token::HatAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Hat)
-                    },
+            },
+            PartialToken::Literal(literal) => {
+                cutoff = 1;
+                if let Some(number) = parse_radix_int_literal(&literal)
+                    .map_err(|error| ParseError::new(error, position))?
+                {
+                    Some(Token::Int(number))
+                } else if let Some(number) = parse_decimal_literal::<IntType>(&literal) {
+                    Some(Token::Int(number))
+                } else if let Some(number) = parse_decimal_literal::<FloatType>(&literal) {
+                    Some(Token::Float(number))
+                } else if let Ok(boolean) = literal.parse::<bool>() {
+                    Some(Token::Boolean(boolean))
+                } else if has_misplaced_digit_separator(&literal) {
+                    return Err(ParseError::new(
+                        EvalexprError::MisplacedDigitSeparator(literal),
+                        position,
+                    ));
+                } else {
+                    // If there are two tokens following this one, check if the next one is
+                    // a plus or a minus. If so, then attempt to parse all three tokens as a
+                    // scientific notation number of the form `<coefficient>e{+,-}<exponent>`,
+                    // for example [Literal("10e"), Minus, Literal("3")] => "1e-3".parse().
+                    match (second, third) {
+                        (Some(second), Some(third))
+                            if second == PartialToken::Minus || second == PartialToken::Plus =>
+                        {
+                            let scientific = format!("{}{}{}", literal, second, third);
+                            if let Some(number) = parse_decimal_literal::<FloatType>(&scientific) {
+                                cutoff = 3;
+                                Some(Token::Float(number))
+                            } else if has_misplaced_digit_separator(&scientific) {
+                                return Err(ParseError::new(
+                                    EvalexprError::MisplacedDigitSeparator(scientific),
+                                    position,
+                                ));
+                            } else {
+                                Some(Token::Identifier(literal.to_string()))
+                            }
+                        },
+                        _ => Some(Token::Identifier(literal.to_string())),
+                    }
+                }
+            },
+            PartialToken::Whitespace => {
+                cutoff = 1;
+                None
+            },
+            PartialToken::Eq => match second {
+                Some(PartialToken::Eq) => Some(Token::Eq),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Assign)
                 },
-                PartialToken::Literal(literal) => {
+            },
+            PartialToken::ExclamationMark => match second {
+                Some(PartialToken::Eq) => Some(Token::Neq),
+                _ => {
                     cutoff = 1;
-                    if let Ok(number) = literal.parse::<IntType>() {
-                        Some(Token::Int(number))
-                    } else if let Ok(number) = literal.parse::<FloatType>() {
-                        Some(Token::Float(number))
-                    } else if let Ok(boolean) = literal.parse::<bool>() {
-                        Some(Token::Boolean(boolean))
-                    } else {
-                        // If there are two tokens following this one, check if the next one is
-                        // a plus or a minus. If so, then attempt to parse all three tokens as a
-                        // scientific notation number of the form `<coefficient>e{+,-}<exponent>`,
-                        // for example [Literal("10e"), Minus, Literal("3")] => "1e-3".parse().
-                        match (second, third) {
-                            (Some(second), Some(third))
-                                if second == PartialToken::Minus
-                                    || second == PartialToken::Plus =>
-                            {
-                                if let Ok(number) =
-                                    format!("{}{}{}", literal, second, third).parse::<FloatType>()
-                                {
-                                    cutoff = 3;
-                                    Some(Token::Float(number))
-                                } else {
-                                    Some(Token::Identifier(literal.to_string()))
-                                }
-                            },
-                            _ => Some(Token::Identifier(literal.to_string())),
-                        }
-                    }
+                    Some(Token::Not)
                 },
-                PartialToken::Whitespace => {
+            },
+            PartialToken::Gt => match second {
+                Some(PartialToken::Eq) => Some(Token::Geq),
+                _ => {
                     cutoff = 1;
-                    None
+                    Some(Token::Gt)
                 },
-                PartialToken::Eq => match second {
-                    Some(PartialToken::Eq) => Some(Token::Eq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Assign)
-                    },
+            },
+            PartialToken::Lt => match second {
+                Some(PartialToken::Eq) => Some(Token::Leq),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Lt)
                 },
-                PartialToken::ExclamationMark => match second {
-                    Some(PartialToken::Eq) => Some(Token::Neq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Not)
+            },
+            PartialToken::Ampersand => match second {
+                Some(PartialToken::Ampersand) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::AndAssign)
                     },
+                    _ => Some(Token::And),
                 },
-                PartialToken::Gt => match second {
-                    Some(PartialToken::Eq) => Some(Token::Geq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Gt)
-                    },
+                _ => {
+                    cutoff = 1;
+                    Some(Token::BitAnd)
                 },
-                PartialToken::Lt => match second {
-                    Some(PartialToken::Eq) => Some(Token::Leq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Lt)
+            },
+            PartialToken::VerticalBar => match second {
+                Some(PartialToken::VerticalBar) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::OrAssign)
                     },
+                    _ => Some(Token::Or),
                 },
-                PartialToken::Ampersand => match second {
-                    Some(PartialToken::Ampersand) => match third {
-                        Some(PartialToken::Eq) => {
-                            cutoff = 3;
-                            Some(Token::AndAssign)
-                        },
-                        _ => Some(Token::And),
-                    },
-                    _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::BitOr)
                 },
-                PartialToken::VerticalBar => match second {
-                    Some(PartialToken::VerticalBar) => match third {
-                        Some(PartialToken::Eq) => {
-                            cutoff = 3;
-                            Some(Token::OrAssign)
-                        },
-                        _ => Some(Token::Or),
-                    },
-                    _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+            },
+            PartialToken::QuestionMark => match second {
+                Some(PartialToken::QuestionMark) => Some(Token::Coalesce),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::QuestionMark)
                 },
-            }
-            .into_iter(),
-        );
+            },
+        };
 
+        result.extend(token.map(|token| (token, position)));
         tokens = &tokens[cutoff..];
     }
     Ok(result)
 }
 
-pub(crate) fn tokenize(string: &str) -> EvalexprResult<Vec<Token>> {
+/// Converts a string to a vector of tokens, each paired with the byte position where it starts.
+pub(crate) fn tokenize_with_positions(string: &str) -> Result<Vec<(Token, usize)>, ParseError> {
     partial_tokens_to_tokens(&str_to_partial_tokens(string)?)
 }
 
+pub(crate) fn tokenize(string: &str) -> EvalexprResult<Vec<Token>> {
+    Ok(tokenize_with_positions(string)?
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::token::{char_to_partial_token, tokenize, Token};
@@ -450,7 +693,8 @@ mod tests {
     #[test]
     fn test_partial_token_display() {
         let chars = vec![
-            '+', '-', '*', '/', '%', '^', '(', ')', ',', ';', '=', '!', '>', '<', '&', '|', ' ',
+            '+', '-', '*', '/', '%', '^', '(', ')', '[', ']', ',', ';', '=', '!', '>', '<', '&',
+            '|', '?', ':', ' ',
         ];
 
         for char in chars {
@@ -464,7 +708,7 @@ mod tests {
     #[test]
     fn test_token_display() {
         let token_string =
-            "+ - * / % ^ == != > < >= <= && || ! ( ) = += -= *= /= %= ^= &&= ||= , ; ";
+            "+ - * ** / // % ^ == != > < >= <= && || ! & | ( ) [ ] = += -= *= /= %= ^= &&= ||= ? : ?? , ; ";
         let tokens = tokenize(token_string).unwrap();
         let mut result_string = String::new();
 
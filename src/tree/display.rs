@@ -1,12 +1,132 @@
-use crate::Node;
+use crate::{operator::Operator, IntType, Node, Value};
 use std::fmt::{Display, Error, Formatter};
 
 impl Display for Node {
+    /// Formats the operator tree as an expression that parses back into an equal tree.
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        self.operator.fmt(f)?;
-        for child in self.children() {
-            write!(f, " {}", child)?;
+        if self.operator() == &Operator::RootNode {
+            // The outermost root node is implicit and has no parentheses
+            write_root_content(self, f)
+        } else {
+            write_node(self, f)
         }
+    }
+}
+
+/// Returns the precedence of the node when written as an expression.
+fn precedence(node: &Node) -> i32 {
+    match node.operator() {
+        // A negative number is written with a leading minus, so it binds like a negation.
+        // `IntType::MIN` is written in parentheses, so it binds like a value.
+        Operator::Const {
+            value: Value::Int(int),
+        } if *int < 0 && *int != IntType::MIN => Operator::Neg.precedence(),
+        Operator::Const {
+            value: Value::Float(float),
+        } if float.is_sign_negative() => Operator::Neg.precedence(),
+        operator => operator.precedence(),
+    }
+}
+
+fn write_node(node: &Node, f: &mut Formatter) -> Result<(), Error> {
+    use crate::operator::Operator::*;
+    let operator = node.operator();
+    let children = node.children();
+    match operator {
+        RootNode => {
+            write!(f, "(")?;
+            write_root_content(node, f)?;
+            write!(f, ")")
+        },
+        Neg | Not => {
+            write!(f, "{}", operator)?;
+            write_operand(
+                &children[0],
+                precedence(&children[0]) < operator.precedence(),
+                f,
+            )
+        },
+        Tuple | Chain => {
+            for (index, child) in children.iter().enumerate() {
+                if index > 0 {
+                    write!(f, "{} ", operator)?;
+                }
+                // Sequence elements are wrapped in implicit root nodes
+                if child.operator() == &RootNode {
+                    if let Some(content) = child.children().first() {
+                        write_operand(content, precedence(content) <= operator.precedence(), f)?;
+                    }
+                } else {
+                    write_operand(child, precedence(child) <= operator.precedence(), f)?;
+                }
+            }
+            // A tuple with a single element is written with a trailing comma
+            if operator == &Tuple && children.len() == 1 {
+                write!(f, ",")?;
+            }
+            Ok(())
+        },
+        Const { value } => write!(f, "{}", value),
+        VariableIdentifierWrite { identifier } | VariableIdentifierRead { identifier } => {
+            write!(f, "{}", identifier)
+        },
+        Index => {
+            let (collection, index) = (&children[0], &children[1]);
+            write_operand(
+                collection,
+                precedence(collection) < operator.precedence(),
+                f,
+            )?;
+            write!(f, "[")?;
+            if index.operator() == &RootNode {
+                write_root_content(index, f)?;
+            } else {
+                write_node(index, f)?;
+            }
+            write!(f, "]")
+        },
+        FunctionIdentifier { identifier } => {
+            write!(f, "{}", identifier)?;
+            let argument = &children[0];
+            if argument.operator() == &RootNode {
+                write_node(argument, f)
+            } else if precedence(argument) < operator.precedence() {
+                write_operand(argument, true, f)
+            } else {
+                write!(f, " ")?;
+                write_node(argument, f)
+            }
+        },
+        _ => {
+            let (left, right) = (&children[0], &children[1]);
+            let (left_precedence, right_precedence) = (precedence(left), precedence(right));
+            let left_parentheses = left_precedence < operator.precedence()
+                || (left_precedence == operator.precedence() && !operator.is_left_to_right());
+            let right_parentheses = right_precedence < operator.precedence()
+                || (right_precedence == operator.precedence() && operator.is_left_to_right());
+
+            write_operand(left, left_parentheses, f)?;
+            write!(f, " {} ", operator)?;
+            write_operand(right, right_parentheses, f)
+        },
+    }
+}
+
+/// Writes the child of a root node without surrounding parentheses.
+fn write_root_content(node: &Node, f: &mut Formatter) -> Result<(), Error> {
+    if let Some(child) = node.children().first() {
+        write_node(child, f)
+    } else {
         Ok(())
     }
 }
+
+fn write_operand(node: &Node, parentheses: bool, f: &mut Formatter) -> Result<(), Error> {
+    if parentheses {
+        write!(f, "(")?;
+        write_node(node, f)?;
+        write!(f, ")")
+    } else {
+        write_node(node, f)
+    }
+}
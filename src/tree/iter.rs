@@ -41,9 +41,135 @@ impl<'a> Iterator for NodeIter<'a> {
     }
 }
 
+/// A cursor that traverses an operator tree in pre-order, yielding the nodes mutably.
+///
+/// A node contains its children, so a yielded node is only borrowed until the next call to `NodeIterMut::next`.
+/// This is why this type does not implement `Iterator`, and is used with `while let` instead of `for`.
+/// The traversal continues with the children that the last yielded node has when `NodeIterMut::next` is called again,
+/// so the children of a node may be restructured or replaced while it is borrowed.
+pub struct NodeIterMut<'a> {
+    root: &'a mut Node,
+    /// The child indices on the path from the root to the last yielded node.
+    path: Vec<usize>,
+    finished: bool,
+}
+
+impl<'a> NodeIterMut<'a> {
+    fn new(node: &'a mut Node) -> Self {
+        NodeIterMut {
+            root: node,
+            path: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the next node in pre-order, or `None` if all nodes were visited.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&mut Node> {
+        if self.finished {
+            return None;
+        }
+
+        // Visit the first child of the last yielded node, or of the root in the first call.
+        if node_at(self.root, &self.path).children.is_empty() {
+            // Otherwise, visit the next sibling of the closest ancestor that has one.
+            loop {
+                let index = match self.path.pop() {
+                    Some(index) => index,
+                    None => {
+                        self.finished = true;
+                        return None;
+                    },
+                };
+                if index + 1 < node_at(self.root, &self.path).children.len() {
+                    self.path.push(index + 1);
+                    break;
+                }
+            }
+        } else {
+            self.path.push(0);
+        }
+
+        Some(
+            self.path
+                .iter()
+                .fold(&mut *self.root, |node, &index| &mut node.children[index]),
+        )
+    }
+}
+
+/// Returns the node that is reached from `node` by following the given child indices.
+fn node_at<'a>(node: &'a Node, path: &[usize]) -> &'a Node {
+    path.iter().fold(node, |node, &index| &node.children[index])
+}
+
+/// An iterator that traverses an operator tree in post-order.
+pub struct NodePostOrderIter<'a> {
+    stack: Vec<(&'a Node, Iter<'a, Node>)>,
+}
+
+impl<'a> NodePostOrderIter<'a> {
+    fn new(node: &'a Node) -> Self {
+        NodePostOrderIter {
+            stack: vec![(node, node.children.iter())],
+        }
+    }
+}
+
+impl<'a> Iterator for NodePostOrderIter<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (_, children) = self.stack.last_mut()?;
+
+            if let Some(child) = children.next() {
+                self.stack.push((child, child.children.iter()));
+            } else {
+                // Can not fail because we just borrowed the last element.
+                let (node, _) = self.stack.pop().unwrap();
+                // The node this iterator was created from is not part of the traversal.
+                if self.stack.is_empty() {
+                    return None;
+                } else {
+                    return Some(node);
+                }
+            }
+        }
+    }
+}
+
 impl Node {
     /// Returns an iterator over all nodes in this tree.
     pub fn iter(&self) -> impl Iterator<Item = &Node> {
         NodeIter::new(self)
     }
+
+    /// Returns a cursor over all nodes in this tree, in the same order as `Node::iter`, that yields the nodes mutably.
+    /// The nodes can be modified in place, e.g. to rename identifiers or to replace subtrees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut tree = build_operator_tree("a + b").unwrap(); // Do proper error handling here
+    /// let mut nodes = tree.iter_mut();
+    /// while let Some(node) = nodes.next() {
+    ///     if let Operator::VariableIdentifierRead { identifier } = node.operator() {
+    ///         *node = build_operator_tree(&format!("len(\"{}\") * 2", identifier)).unwrap();
+    ///     }
+    /// }
+    /// assert_eq!(tree.eval(), Ok(Value::from(4)));
+    /// ```
+    pub fn iter_mut(&mut self) -> NodeIterMut<'_> {
+        NodeIterMut::new(self)
+    }
+
+    /// Returns an iterator over all nodes in this tree, visiting the children of each node before the node itself.
+    ///
+    /// Like `Node::iter`, the iterator visits all nodes below this node, but not this node itself.
+    pub fn iter_post_order(&self) -> impl Iterator<Item = &Node> {
+        NodePostOrderIter::new(self)
+    }
 }
@@ -1,26 +1,29 @@
 use crate::{
     token::Token,
     value::{TupleType, EMPTY_VALUE},
-    Context, ContextWithMutableVariables, EmptyType, FloatType, HashMapContext, IntType,
+    Context, ContextWithMutableVariables, EmptyContext, EmptyType, FloatType, HashMapContext,
+    IntType,
 };
 
 use crate::{
-    error::{EvalexprError, EvalexprResult},
+    error::{EvalexprError, EvalexprResult, ParseError},
     operator::*,
     value::Value,
 };
-use std::mem;
+use std::{fmt, mem, str::FromStr};
 
-// Exclude display module from coverage, as it prints not well-defined prefix notation.
-#[cfg(not(tarpaulin_include))]
 mod display;
 mod iter;
 
+pub use iter::NodeIterMut;
+
 /// A node in the operator tree.
 /// The operator tree is created by the crate-level `build_operator_tree` method.
 /// It can be evaluated for a given context with the `Node::eval` method.
 ///
 /// The advantage of constructing the operator tree separately from the actual evaluation is that it can be evaluated arbitrarily often with different contexts.
+/// The expression is tokenized and parsed only once, so a `Node` is the compiled form of an expression.
+/// Besides `build_operator_tree`, a `Node` can also be obtained with `str::parse`.
 ///
 /// # Examples
 ///
@@ -31,12 +34,17 @@ mod iter;
 /// context.set_value("alpha".into(), 2.into()).unwrap(); // Do proper error handling here
 /// let node = build_operator_tree("1 + alpha").unwrap(); // Do proper error handling here
 /// assert_eq!(node.eval_with_context(&context), Ok(Value::from(3)));
+///
+/// let node: Node = "alpha * 2".parse().unwrap(); // Do proper error handling here
+/// assert_eq!(node.eval_int_with_context(&context), Ok(4));
 /// ```
 ///
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Clone)]
 pub struct Node {
     operator: Operator,
     children: Vec<Node>,
+    /// The byte position of the token this node was parsed from, used to report errors found after parsing.
+    position: usize,
 }
 
 impl Node {
@@ -44,9 +52,15 @@ impl Node {
         Self {
             children: Vec::new(),
             operator,
+            position: 0,
         }
     }
 
+    fn at(mut self, position: usize) -> Self {
+        self.position = position;
+        self
+    }
+
     fn root_node() -> Self {
         Self::new(Operator::RootNode)
     }
@@ -76,8 +90,9 @@ impl Node {
         })
     }
 
-    /// Returns an iterator over all variable identifiers in this expression.
-    /// Each occurrence of a variable identifier is returned separately.
+    /// Returns an iterator over all variable identifiers in this expression, in the order of `Node::iter`.
+    /// Each occurrence of a variable identifier is returned separately, so the iterator may yield duplicates.
+    /// To get each identifier only once, collect the iterator into a set.
     ///
     /// # Examples
     ///
@@ -141,8 +156,9 @@ impl Node {
         })
     }
 
-    /// Returns an iterator over all function identifiers in this expression.
+    /// Returns an iterator over all function identifiers in this expression, in the order of `Node::iter`.
     /// Each occurrence of a function identifier is returned separately.
+    /// This includes builtin functions, so it can be used to check that an expression only calls allowed functions before evaluating it.
     ///
     /// # Examples
     ///
@@ -165,6 +181,10 @@ impl Node {
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_with_context<C: Context>(&self, context: &C) -> EvalexprResult<Value> {
+        if let Some(result) = self.eval_short_circuit(|node| node.eval_with_context(context)) {
+            return result;
+        }
+
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context(context)?);
@@ -172,6 +192,49 @@ impl Node {
         self.operator().eval(&arguments, context)
     }
 
+    /// Evaluates the operator tree rooted at this node with the given context,
+    /// failing with `EvalexprError::BudgetExceeded` if more than `budget` operators would need to be evaluated.
+    ///
+    /// Each evaluated node of the tree counts as one operator, including the root nodes created for parentheses.
+    /// Nodes that are skipped by short-circuiting operators like `&&` are not counted.
+    ///
+    /// Fails, if one of the operators in the expression tree fails.
+    pub fn eval_with_context_and_budget<C: Context>(
+        &self,
+        context: &C,
+        budget: usize,
+    ) -> EvalexprResult<Value> {
+        let mut remaining_budget = budget;
+        self.eval_with_context_and_remaining_budget(context, budget, &mut remaining_budget)
+    }
+
+    fn eval_with_context_and_remaining_budget<C: Context>(
+        &self,
+        context: &C,
+        budget: usize,
+        remaining_budget: &mut usize,
+    ) -> EvalexprResult<Value> {
+        *remaining_budget = remaining_budget
+            .checked_sub(1)
+            .ok_or(EvalexprError::BudgetExceeded { budget })?;
+
+        if let Some(result) = self.eval_short_circuit(|node| {
+            node.eval_with_context_and_remaining_budget(context, budget, remaining_budget)
+        }) {
+            return result;
+        }
+
+        let mut arguments = Vec::new();
+        for child in self.children() {
+            arguments.push(child.eval_with_context_and_remaining_budget(
+                context,
+                budget,
+                remaining_budget,
+            )?);
+        }
+        self.operator().eval(&arguments, context)
+    }
+
     /// Evaluates the operator tree rooted at this node with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -179,6 +242,10 @@ impl Node {
         &self,
         context: &mut C,
     ) -> EvalexprResult<Value> {
+        if let Some(result) = self.eval_short_circuit(|node| node.eval_with_context_mut(context)) {
+            return result;
+        }
+
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context_mut(context)?);
@@ -446,6 +513,203 @@ impl Node {
         &mut self.operator
     }
 
+    /// Returns the amount of nodes in the tree below this node, i.e. the amount of nodes returned by `Node::iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("a + 2 * b").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.len(), 5);
+    /// assert_eq!(tree.depth(), 3);
+    /// ```
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns the maximum nesting depth of the tree below this node.
+    /// A node without children has depth zero, and otherwise the depth of a node is one more than the maximum depth of its children.
+    /// This does not recurse, so it is safe to use on arbitrarily deep trees.
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack: Vec<(&Node, usize)> = vec![(self, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            stack.extend(node.children.iter().map(|child| (child, depth + 1)));
+        }
+        max_depth
+    }
+
+    /// Returns a copy of this tree in which all subtrees that evaluate to a constant are replaced by that constant.
+    /// The returned tree evaluates to the same value as this tree, but potentially with less work.
+    ///
+    /// A subtree is considered constant if it does not contain any variable identifiers or function calls.
+    /// Function calls are never folded, even if their arguments are constant, because functions might not be pure, like the builtin function `random`.
+    /// Subtrees that fail to evaluate are kept, such that the error is reported when evaluating the returned tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("2 * 3 + x").unwrap(); // Do proper error handling here
+    /// let folded = tree.fold_constants();
+    /// assert_eq!(folded.to_string(), "6 + x");
+    /// assert_eq!(folded.eval_with_context(&context_map! { "x" => 1 }.unwrap()), Ok(Value::from(7)));
+    /// ```
+    pub fn fold_constants(&self) -> Node {
+        let mut result = self.clone();
+        result.fold_constants_in_place();
+        result
+    }
+
+    /// Returns a copy of this tree in which every read of the variable `identifier` is replaced by the constant `value`.
+    /// If the variable is not read in this tree, the copy is equal to this tree.
+    ///
+    /// Assignments to the variable are not replaced, as a constant cannot be assigned to.
+    /// However, reads of the variable after an assignment are replaced as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("x + y").unwrap(); // Do proper error handling here
+    /// let substituted = tree.substitute("x", Value::from(5));
+    /// assert_eq!(substituted.to_string(), "5 + y");
+    /// assert_eq!(substituted.eval_with_context(&context_map! { "y" => 1 }.unwrap()), Ok(Value::from(6)));
+    /// ```
+    pub fn substitute(&self, identifier: &str, value: Value) -> Node {
+        let mut result = self.clone();
+        let mut nodes = result.iter_mut();
+        while let Some(node) = nodes.next() {
+            if matches!(node.operator(), Operator::VariableIdentifierRead { identifier: read } if read == identifier)
+            {
+                node.operator = Operator::value(value.clone());
+            }
+        }
+        result
+    }
+
+    /// Folds all constant subtrees of this node and returns true if this node is constant itself.
+    fn fold_constants_in_place(&mut self) -> bool {
+        let mut is_constant = !matches!(
+            self.operator(),
+            Operator::VariableIdentifierWrite { .. }
+                | Operator::VariableIdentifierRead { .. }
+                | Operator::FunctionIdentifier { .. }
+        );
+        for child in &mut self.children {
+            // Fold all children, even if this node is already known to not be constant
+            is_constant &= child.fold_constants_in_place();
+        }
+
+        // Root nodes are kept to preserve the structure of parentheses
+        if is_constant && !self.children.is_empty() && self.operator() != &Operator::RootNode {
+            match self.eval_with_context(&EmptyContext) {
+                Ok(value) => *self = Node::new(Operator::value(value)).at(self.position),
+                Err(_) => return false,
+            }
+        }
+        is_constant
+    }
+
+    /// Evaluates this node if its operator does not evaluate all of its children, using `eval` to evaluate the children that are needed.
+    /// Returns `None` if the operator needs all of its children to be evaluated.
+    fn eval_short_circuit<F>(&self, mut eval: F) -> Option<EvalexprResult<Value>>
+    where
+        F: FnMut(&Node) -> EvalexprResult<Value>,
+    {
+        match (self.operator(), self.children.as_slice()) {
+            (Operator::ConditionalElse, [conditional, alternative]) => {
+                if let (Operator::Conditional, [condition, consequence]) =
+                    (conditional.operator(), conditional.children.as_slice())
+                {
+                    Some(
+                        match eval(condition).and_then(|condition| condition.as_boolean()) {
+                            Ok(true) => eval(consequence),
+                            Ok(false) => eval(alternative),
+                            Err(error) => Err(error),
+                        },
+                    )
+                } else {
+                    Some(Err(EvalexprError::UnmatchedConditional))
+                }
+            },
+            (Operator::ConditionalElse, _) => Some(Err(EvalexprError::UnmatchedConditional)),
+            (Operator::Coalesce, [value, default]) => Some(match eval(value) {
+                Ok(Value::Empty) => eval(default),
+                result => result,
+            }),
+            (Operator::And, [left, right]) => {
+                Some(match eval(left).and_then(|left| left.as_boolean()) {
+                    Ok(false) => Ok(Value::Boolean(false)),
+                    Ok(true) => {
+                        eval(right).and_then(|right| Ok(Value::Boolean(right.as_boolean()?)))
+                    },
+                    Err(error) => Err(error),
+                })
+            },
+            (Operator::Or, [left, right]) => {
+                Some(match eval(left).and_then(|left| left.as_boolean()) {
+                    Ok(true) => Ok(Value::Boolean(true)),
+                    Ok(false) => {
+                        eval(right).and_then(|right| Ok(Value::Boolean(right.as_boolean()?)))
+                    },
+                    Err(error) => Err(error),
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks that this tree is at most `max_depth` deep.
+    /// On failure, sets `position` to the position of the first node that is too deep.
+    fn check_depth(&self, max_depth: usize, position: &mut usize) -> EvalexprResult<()> {
+        let mut stack: Vec<(&Node, usize)> = vec![(self, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if depth > max_depth {
+                *position = node.position;
+                return Err(EvalexprError::MaxDepthExceeded { max_depth });
+            }
+            // Push in reverse to visit the children in the order they appear in the expression
+            stack.extend(node.children.iter().rev().map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// Checks that each `Conditional` is the first child of a `ConditionalElse` and vice versa,
+    /// and that no comparison is a direct child of another comparison.
+    /// On failure, sets `position` to the position of the offending operator.
+    fn check_nesting(&self, position: &mut usize) -> EvalexprResult<()> {
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            for (index, child) in node.children.iter().enumerate() {
+                let is_conditional = child.operator() == &Operator::Conditional;
+                let expects_conditional =
+                    node.operator() == &Operator::ConditionalElse && index == 0;
+                if is_conditional != expects_conditional {
+                    // Either the `?` has no matching `:`, or the `:` has no matching `?`
+                    *position = if is_conditional {
+                        child.position
+                    } else {
+                        node.position
+                    };
+                    return Err(EvalexprError::UnmatchedConditional);
+                }
+                if node.operator().is_comparison() && child.operator().is_comparison() {
+                    // Point to the second comparison of the chain
+                    *position = node.position.max(child.position);
+                    return Err(EvalexprError::ChainedComparison);
+                }
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        Ok(())
+    }
+
     fn has_enough_children(&self) -> bool {
         Some(self.children().len()) == self.operator().max_argument_amount()
     }
@@ -458,7 +722,19 @@ impl Node {
         }
     }
 
-    fn insert_back_prioritized(&mut self, node: Node, is_root_node: bool) -> EvalexprResult<()> {
+    /// Inserts `node` into the tree rooted at this node, which is at `depth` below the node the insertion started at.
+    /// Fails if the insertion would recurse deeper than `max_depth`.
+    fn insert_back_prioritized(
+        &mut self,
+        node: Node,
+        is_root_node: bool,
+        depth: usize,
+        max_depth: usize,
+    ) -> EvalexprResult<()> {
+        if depth >= max_depth {
+            return Err(EvalexprError::MaxDepthExceeded { max_depth });
+        }
+
         // println!("Inserting {:?} into {:?}", node.operator, self.operator());
         if self.operator().precedence() < node.operator().precedence() || is_root_node
             // Right-to-left chaining
@@ -478,10 +754,12 @@ impl Node {
                 {
                     // println!("Recursing into {:?}", self.children.last().unwrap().operator());
                     // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
-                    self.children
-                        .last_mut()
-                        .unwrap()
-                        .insert_back_prioritized(node, false)
+                    self.children.last_mut().unwrap().insert_back_prioritized(
+                        node,
+                        false,
+                        depth + 1,
+                        max_depth,
+                    )
                 } else {
                     // println!("Rotating");
                     if node.operator().is_leaf() {
@@ -531,6 +809,41 @@ impl Node {
     }
 }
 
+/// Compares the operators and children of the nodes, but not the positions they were parsed at.
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.operator == other.operator && self.children == other.children
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("operator", &self.operator)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+impl Drop for Node {
+    /// Drops the children iteratively, such that dropping deep trees does not overflow the stack.
+    fn drop(&mut self) {
+        let mut nodes = mem::take(&mut self.children);
+        while let Some(mut node) = nodes.pop() {
+            nodes.append(&mut node.children);
+        }
+    }
+}
+
+impl FromStr for Node {
+    type Err = EvalexprError;
+
+    /// Parses the given expression into an operator tree, just like `build_operator_tree`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        crate::build_operator_tree(string)
+    }
+}
+
 fn collapse_root_stack_to(
     root_stack: &mut Vec<Node>,
     mut root: Node,
@@ -541,6 +854,7 @@ fn collapse_root_stack_to(
             // TODO I'm not sure about this >, as I have no example for different sequence operators with the same precedence
             if potential_higher_root.operator().precedence() > collapse_goal.operator().precedence()
             {
+                remove_trailing_comma(&mut root);
                 potential_higher_root.children.push(root);
                 root = potential_higher_root;
             } else {
@@ -556,6 +870,20 @@ fn collapse_root_stack_to(
     Ok(root)
 }
 
+/// Removes the empty element after a trailing comma from a finished tuple like `(1, 2,)`,
+/// unless the element before it is empty as well, like in `(,)`.
+fn remove_trailing_comma(sequence: &mut Node) {
+    let is_empty_element =
+        |node: &Node| node.operator() == &Operator::RootNode && node.children.is_empty();
+    if sequence.operator() == &Operator::Tuple {
+        if let [.., previous, last] = sequence.children.as_slice() {
+            if is_empty_element(last) && !is_empty_element(previous) {
+                sequence.children.pop();
+            }
+        }
+    }
+}
+
 fn collapse_all_sequences(root_stack: &mut Vec<Node>) -> EvalexprResult<()> {
     // println!("Collapsing all sequences");
     // println!("Initial root stack is: {:?}", root_stack);
@@ -579,6 +907,7 @@ fn collapse_all_sequences(root_stack: &mut Vec<Node>) -> EvalexprResult<()> {
 
         if let Some(mut potential_higher_root) = root_stack.pop() {
             if root.operator().is_sequence() {
+                remove_trailing_comma(&mut root);
                 potential_higher_root.children.push(root);
                 root = potential_higher_root;
             } else {
@@ -601,83 +930,127 @@ fn collapse_all_sequences(root_stack: &mut Vec<Node>) -> EvalexprResult<()> {
     Ok(())
 }
 
-pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node> {
+/// Builds an operator tree from tokens paired with their byte positions in an expression of the given length.
+///
+/// Errors are returned together with the position of the token that caused them.
+pub(crate) fn tokens_to_operator_tree(
+    tokens: Vec<(Token, usize)>,
+    length: usize,
+    max_depth: usize,
+) -> Result<Node, ParseError> {
+    let mut position = length;
+    build_operator_tree(tokens, length, max_depth, &mut position)
+        .map_err(|error| ParseError::new(error, position))
+}
+
+/// Builds an operator tree, setting `position` to the position of the token that caused an error.
+fn build_operator_tree(
+    tokens: Vec<(Token, usize)>,
+    length: usize,
+    max_depth: usize,
+    position: &mut usize,
+) -> EvalexprResult<Node> {
     let mut root_stack = vec![Node::root_node()];
+    let mut brace_positions = Vec::new();
     let mut last_token_is_rightsided_value = false;
     let mut token_iter = tokens.iter().peekable();
 
-    while let Some(token) = token_iter.next().cloned() {
-        let next = token_iter.peek().cloned();
+    while let Some((token, token_position)) = token_iter.next().cloned() {
+        *position = token_position;
+        let next = token_iter.peek().map(|(token, _)| token);
+        let new_node = |operator| Node::new(operator).at(token_position);
 
         let node = match token.clone() {
-            Token::Plus => Some(Node::new(Operator::Add)),
+            Token::Plus => Some(new_node(Operator::Add)),
             Token::Minus => {
                 if last_token_is_rightsided_value {
-                    Some(Node::new(Operator::Sub))
+                    Some(new_node(Operator::Sub))
                 } else {
-                    Some(Node::new(Operator::Neg))
+                    Some(new_node(Operator::Neg))
                 }
             },
-            Token::Star => Some(Node::new(Operator::Mul)),
-            Token::Slash => Some(Node::new(Operator::Div)),
-            Token::Percent => Some(Node::new(Operator::Mod)),
-            Token::Hat => Some(Node::new(Operator::Exp)),
-
-            Token::Eq => Some(Node::new(Operator::Eq)),
-            Token::Neq => Some(Node::new(Operator::Neq)),
-            Token::Gt => Some(Node::new(Operator::Gt)),
-            Token::Lt => Some(Node::new(Operator::Lt)),
-            Token::Geq => Some(Node::new(Operator::Geq)),
-            Token::Leq => Some(Node::new(Operator::Leq)),
-            Token::And => Some(Node::new(Operator::And)),
-            Token::Or => Some(Node::new(Operator::Or)),
-            Token::Not => Some(Node::new(Operator::Not)),
+            Token::Star => Some(new_node(Operator::Mul)),
+            Token::Slash => Some(new_node(Operator::Div)),
+            Token::DoubleSlash => Some(new_node(Operator::FloorDiv)),
+            Token::Percent => Some(new_node(Operator::Mod)),
+            Token::Hat | Token::DoubleStar => Some(new_node(Operator::Exp)),
+
+            Token::Eq => Some(new_node(Operator::Eq)),
+            Token::Neq => Some(new_node(Operator::Neq)),
+            Token::Gt => Some(new_node(Operator::Gt)),
+            Token::Lt => Some(new_node(Operator::Lt)),
+            Token::Geq => Some(new_node(Operator::Geq)),
+            Token::Leq => Some(new_node(Operator::Leq)),
+            Token::And => Some(new_node(Operator::And)),
+            Token::Or => Some(new_node(Operator::Or)),
+            Token::Not => Some(new_node(Operator::Not)),
+
+            Token::BitAnd => Some(new_node(Operator::BitAnd)),
+            Token::BitOr => Some(new_node(Operator::BitOr)),
+
+            Token::QuestionMark => Some(new_node(Operator::Conditional)),
+            Token::Colon => Some(new_node(Operator::ConditionalElse)),
+            Token::Coalesce => Some(new_node(Operator::Coalesce)),
 
             Token::LBrace => {
-                root_stack.push(Node::root_node());
+                if root_stack.len() > max_depth {
+                    return Err(EvalexprError::MaxDepthExceeded { max_depth });
+                }
+                root_stack.push(Node::root_node().at(token_position));
+                brace_positions.push((Token::LBrace, token_position));
                 None
             },
-            Token::RBrace => {
-                if root_stack.len() <= 1 {
-                    return Err(EvalexprError::UnmatchedRBrace);
+            // The root node for the index is pushed after the indexing operator has been inserted below
+            Token::LBracket => Some(new_node(Operator::Index)),
+            Token::RBrace | Token::RBracket => {
+                let (opening_brace, unmatched_error) = if token == Token::RBrace {
+                    (Token::LBrace, EvalexprError::UnmatchedRBrace)
+                } else {
+                    (Token::LBracket, EvalexprError::UnmatchedRBracket)
+                };
+                if root_stack.len() <= 1
+                    || brace_positions.last().map(|(brace, _)| brace) != Some(&opening_brace)
+                {
+                    return Err(unmatched_error);
                 } else {
                     collapse_all_sequences(&mut root_stack)?;
+                    brace_positions.pop();
                     root_stack.pop()
                 }
             },
 
-            Token::Assign => Some(Node::new(Operator::Assign)),
-            Token::PlusAssign => Some(Node::new(Operator::AddAssign)),
-            Token::MinusAssign => Some(Node::new(Operator::SubAssign)),
-            Token::StarAssign => Some(Node::new(Operator::MulAssign)),
-            Token::SlashAssign => Some(Node::new(Operator::DivAssign)),
-            Token::PercentAssign => Some(Node::new(Operator::ModAssign)),
-            Token::HatAssign => Some(Node::new(Operator::ExpAssign)),
-            Token::AndAssign => Some(Node::new(Operator::AndAssign)),
-            Token::OrAssign => Some(Node::new(Operator::OrAssign)),
+            Token::Assign => Some(new_node(Operator::Assign)),
+            Token::PlusAssign => Some(new_node(Operator::AddAssign)),
+            Token::MinusAssign => Some(new_node(Operator::SubAssign)),
+            Token::StarAssign => Some(new_node(Operator::MulAssign)),
+            Token::SlashAssign => Some(new_node(Operator::DivAssign)),
+            Token::PercentAssign => Some(new_node(Operator::ModAssign)),
+            Token::HatAssign => Some(new_node(Operator::ExpAssign)),
+            Token::AndAssign => Some(new_node(Operator::AndAssign)),
+            Token::OrAssign => Some(new_node(Operator::OrAssign)),
 
-            Token::Comma => Some(Node::new(Operator::Tuple)),
-            Token::Semicolon => Some(Node::new(Operator::Chain)),
+            Token::Comma => Some(new_node(Operator::Tuple)),
+            Token::Semicolon => Some(new_node(Operator::Chain)),
 
             Token::Identifier(identifier) => {
-                let mut result = Some(Node::new(Operator::variable_identifier_read(
+                let mut result = Some(new_node(Operator::variable_identifier_read(
                     identifier.clone(),
                 )));
                 if let Some(next) = next {
                     if next.is_assignment() {
-                        result = Some(Node::new(Operator::variable_identifier_write(
+                        result = Some(new_node(Operator::variable_identifier_write(
                             identifier.clone(),
                         )));
                     } else if next.is_leftsided_value() {
-                        result = Some(Node::new(Operator::function_identifier(identifier)));
+                        result = Some(new_node(Operator::function_identifier(identifier)));
                     }
                 }
                 result
             },
-            Token::Float(float) => Some(Node::new(Operator::value(Value::Float(float)))),
-            Token::Int(int) => Some(Node::new(Operator::value(Value::Int(int)))),
-            Token::Boolean(boolean) => Some(Node::new(Operator::value(Value::Boolean(boolean)))),
-            Token::String(string) => Some(Node::new(Operator::value(Value::String(string)))),
+            Token::Float(float) => Some(new_node(Operator::value(Value::Float(float)))),
+            Token::Int(int) => Some(new_node(Operator::value(Value::Int(int)))),
+            Token::Boolean(boolean) => Some(new_node(Operator::value(Value::Boolean(boolean)))),
+            Token::String(string) => Some(new_node(Operator::value(Value::String(string)))),
         };
 
         if let Some(mut node) = node {
@@ -689,13 +1062,13 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
                     // If root.operator() and node.operator() are of the same variant, ...
                     if mem::discriminant(root.operator()) == mem::discriminant(node.operator()) {
                         // ... we create a new root node for the next expression in the sequence
-                        root.children.push(Node::root_node());
+                        root.children.push(Node::root_node().at(token_position));
                         root_stack.push(root);
                     } else if root.operator() == &Operator::RootNode {
                         // If the current root is an actual root node, we start a new sequence
                         node.children.push(root);
-                        node.children.push(Node::root_node());
-                        root_stack.push(Node::root_node());
+                        node.children.push(Node::root_node().at(token_position));
+                        root_stack.push(Node::root_node().at(token_position));
                         root_stack.push(node);
                     } else {
                         // Otherwise, we combine the sequences based on their precedences
@@ -704,7 +1077,7 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
                             // If the new sequence has a higher precedence, it is part of the last element of the current root sequence
                             if let Some(last_root_child) = root.children.pop() {
                                 node.children.push(last_root_child);
-                                node.children.push(Node::root_node());
+                                node.children.push(Node::root_node().at(token_position));
                                 root_stack.push(root);
                                 root_stack.push(node);
                             } else {
@@ -721,7 +1094,7 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
                 // println!("Stack after sequence operation: {:?}", root_stack);
                 } else if root.operator().is_sequence() {
                     if let Some(mut last_root_child) = root.children.pop() {
-                        last_root_child.insert_back_prioritized(node, true)?;
+                        last_root_child.insert_back_prioritized(node, true, 0, max_depth)?;
                         root.children.push(last_root_child);
                         root_stack.push(root);
                     } else {
@@ -729,7 +1102,7 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
                         unreachable!()
                     }
                 } else {
-                    root.insert_back_prioritized(node, true)?;
+                    root.insert_back_prioritized(node, true, 0, max_depth)?;
                     root_stack.push(root);
                 }
             } else {
@@ -737,15 +1110,37 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
             }
         }
 
+        if token == Token::LBracket {
+            if root_stack.len() > max_depth {
+                return Err(EvalexprError::MaxDepthExceeded { max_depth });
+            }
+            root_stack.push(Node::root_node().at(token_position));
+            brace_positions.push((Token::LBracket, token_position));
+        }
+
         last_token_is_rightsided_value = token.is_rightsided_value();
     }
 
     // In the end, all sequences are implicitly terminated
+    *position = length;
     collapse_all_sequences(&mut root_stack)?;
 
     if root_stack.len() > 1 {
-        Err(EvalexprError::UnmatchedLBrace)
+        match brace_positions.last() {
+            Some((Token::LBracket, brace_position)) => {
+                *position = *brace_position;
+                Err(EvalexprError::UnmatchedLBracket)
+            },
+            Some((_, brace_position)) => {
+                *position = *brace_position;
+                Err(EvalexprError::UnmatchedLBrace)
+            },
+            None => Err(EvalexprError::UnmatchedLBrace),
+        }
     } else if let Some(root) = root_stack.pop() {
+        // Operator trees are evaluated recursively, so check the depth before anything else recurses
+        root.check_depth(max_depth, position)?;
+        root.check_nesting(position)?;
         Ok(root)
     } else {
         Err(EvalexprError::UnmatchedRBrace)
@@ -1,12 +1,24 @@
 use std::fmt::{Display, Error, Formatter};
 
-use crate::Value;
+use crate::{IntType, Value};
 
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self {
-            Value::String(string) => write!(f, "\"{}\"", string),
-            Value::Float(float) => write!(f, "{}", float),
+            Value::String(string) => {
+                write!(f, "\"")?;
+                for c in string.chars() {
+                    if c == '"' || c == '\\' {
+                        write!(f, "\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                write!(f, "\"")
+            },
+            // The debug representation always contains a decimal point or an exponent
+            Value::Float(float) => write!(f, "{:?}", float),
+            // The literal of the absolute value of `IntType::MIN` does not fit into an `IntType`
+            Value::Int(int) if *int == IntType::MIN => write!(f, "({} - 1)", IntType::MIN + 1),
             Value::Int(int) => write!(f, "{}", int),
             Value::Boolean(boolean) => write!(f, "{}", boolean),
             Value::Tuple(tuple) => {
@@ -20,6 +32,10 @@ impl Display for Value {
                     }
                     value.fmt(f)?;
                 }
+                // Without the trailing comma, a single element in parentheses would not be a tuple
+                if tuple.len() == 1 {
+                    write!(f, ",")?;
+                }
                 write!(f, ")")
             },
             Value::Empty => write!(f, "()"),
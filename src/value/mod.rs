@@ -1,7 +1,16 @@
-use crate::error::{EvalexprError, EvalexprResult};
-use std::convert::TryFrom;
+use crate::{
+    error::{EvalexprError, EvalexprResult},
+    token::{tokenize, Token},
+};
+use std::{
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    mem,
+    str::FromStr,
+};
 
 mod display;
+pub(crate) mod ordering;
 pub mod value_type;
 
 /// The type used to represent integers in `Value::Int`.
@@ -123,6 +132,14 @@ impl Value {
         }
     }
 
+    /// Returns an iterator over the elements of the tuple stored in `self` without cloning them, or returns `Err` if `self` is not a `Value::Tuple`.
+    pub fn as_tuple_iter(&self) -> EvalexprResult<std::slice::Iter<'_, Value>> {
+        match self {
+            Value::Tuple(tuple) => Ok(tuple.iter()),
+            value => Err(EvalexprError::expected_tuple(value.clone())),
+        }
+    }
+
     /// Clones the value stored in `self` as `TupleType` or returns `Err` if `self` is not a `Value::Tuple` of the required length.
     pub fn as_fixed_len_tuple(&self, len: usize) -> EvalexprResult<TupleType> {
         match self {
@@ -137,6 +154,46 @@ impl Value {
         }
     }
 
+    /// Moves the value stored in `self` out as `String`, or returns `Err` if `self` is not a `Value::String`.
+    pub fn into_string(self) -> EvalexprResult<String> {
+        match self {
+            Value::String(string) => Ok(string),
+            value => Err(EvalexprError::expected_string(value)),
+        }
+    }
+
+    /// Moves the value stored in `self` out as `IntType`, or returns `Err` if `self` is not a `Value::Int`.
+    pub fn into_int(self) -> EvalexprResult<IntType> {
+        match self {
+            Value::Int(i) => Ok(i),
+            value => Err(EvalexprError::expected_int(value)),
+        }
+    }
+
+    /// Moves the value stored in `self` out as `FloatType`, or returns `Err` if `self` is not a `Value::Float`.
+    pub fn into_float(self) -> EvalexprResult<FloatType> {
+        match self {
+            Value::Float(f) => Ok(f),
+            value => Err(EvalexprError::expected_float(value)),
+        }
+    }
+
+    /// Moves the value stored in `self` out as `bool`, or returns `Err` if `self` is not a `Value::Boolean`.
+    pub fn into_boolean(self) -> EvalexprResult<bool> {
+        match self {
+            Value::Boolean(boolean) => Ok(boolean),
+            value => Err(EvalexprError::expected_boolean(value)),
+        }
+    }
+
+    /// Moves the value stored in `self` out as `TupleType`, or returns `Err` if `self` is not a `Value::Tuple`.
+    pub fn into_tuple(self) -> EvalexprResult<TupleType> {
+        match self {
+            Value::Tuple(tuple) => Ok(tuple),
+            value => Err(EvalexprError::expected_tuple(value)),
+        }
+    }
+
     /// Returns `()`, or returns`Err` if `self` is not a `Value::Tuple`.
     pub fn as_empty(&self) -> EvalexprResult<()> {
         match self {
@@ -146,6 +203,118 @@ impl Value {
     }
 }
 
+/// Parses a single literal into a value, without evaluating an expression.
+///
+/// Accepted are integer, float and boolean literals, optionally negated numbers, string literals in double quotes, and `()` for the empty value.
+/// Anything else, like an expression `1 + 2` or a tuple, results in an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!("-42".parse(), Ok(Value::from(-42)));
+/// assert_eq!("\"a string\"".parse(), Ok(Value::from("a string")));
+/// assert!("1 + 2".parse::<Value>().is_err());
+/// ```
+impl FromStr for Value {
+    type Err = EvalexprError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match tokenize(string)?.as_slice() {
+            [Token::Int(int)] => Ok(Value::Int(*int)),
+            [Token::Minus, Token::Int(int)] => Ok(Value::Int(-int)),
+            [Token::Float(float)] => Ok(Value::Float(*float)),
+            [Token::Minus, Token::Float(float)] => Ok(Value::Float(-float)),
+            [Token::Boolean(boolean)] => Ok(Value::Boolean(*boolean)),
+            [Token::String(string)] => Ok(Value::String(string.clone())),
+            [Token::LBrace, Token::RBrace] => Ok(Value::Empty),
+            _ => Err(EvalexprError::CustomMessage(format!(
+                "Expected a single literal value, but got {:?}",
+                string
+            ))),
+        }
+    }
+}
+
+/// A wrapper around a `Value` that implements `Eq` and `Hash`, so values can be used as keys of hash maps and sets.
+///
+/// `Value` itself does not implement `Eq`, because floats compared with `==` are not reflexive, as `NaN` is not equal to itself.
+/// This wrapper compares floats by their bit pattern instead, after treating `-0.0` as `0.0`.
+/// Hence, `0.0` and `-0.0` are equal like with `==`, and `NaN` is equal to itself.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+/// use std::collections::HashSet;
+///
+/// let mut set = HashSet::new();
+/// set.insert(HashableValue(Value::from(FloatType::NAN)));
+/// assert!(set.contains(&HashableValue(Value::from(FloatType::NAN))));
+/// assert!(!set.contains(&HashableValue(Value::from(0.0))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashableValue(pub Value);
+
+impl HashableValue {
+    /// Returns the bit pattern that identifies the given float, which is the same for `0.0` and `-0.0`.
+    fn float_bits(float: FloatType) -> u64 {
+        if float == 0.0 {
+            (0.0 as FloatType).to_bits()
+        } else {
+            float.to_bits()
+        }
+    }
+
+    fn eq_values(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Float(a), Value::Float(b)) => Self::float_bits(*a) == Self::float_bits(*b),
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| Self::eq_values(a, b))
+            },
+            (a, b) => a == b,
+        }
+    }
+
+    fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+        mem::discriminant(value).hash(state);
+        match value {
+            Value::String(string) => string.hash(state),
+            Value::Float(float) => Self::float_bits(*float).hash(state),
+            Value::Int(int) => int.hash(state),
+            Value::Boolean(boolean) => boolean.hash(state),
+            Value::Tuple(tuple) => {
+                tuple.len().hash(state);
+                for value in tuple {
+                    Self::hash_value(value, state);
+                }
+            },
+            Value::Empty => {},
+        }
+    }
+}
+
+impl PartialEq for HashableValue {
+    fn eq(&self, other: &Self) -> bool {
+        Self::eq_values(&self.0, &other.0)
+    }
+}
+
+impl Eq for HashableValue {}
+
+impl Hash for HashableValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Self::hash_value(&self.0, state);
+    }
+}
+
+impl From<Value> for HashableValue {
+    fn from(value: Value) -> Self {
+        HashableValue(value)
+    }
+}
+
 impl From<String> for Value {
     fn from(string: String) -> Self {
         Value::String(string)
@@ -268,7 +437,14 @@ impl TryFrom<Value> for () {
 
 #[cfg(test)]
 mod tests {
-    use crate::value::{TupleType, Value};
+    use crate::{
+        error::EvalexprError,
+        value::{FloatType, HashableValue, IntType, TupleType, Value},
+    };
+    use std::{
+        collections::{HashMap, HashSet},
+        convert::TryInto,
+    };
 
     #[test]
     fn test_value_conversions() {
@@ -293,4 +469,177 @@ mod tests {
         assert!(Value::from(true).is_boolean());
         assert!(Value::from(TupleType::new()).is_tuple());
     }
+
+    #[test]
+    fn test_hashable_value_map_keys() {
+        let key = |value: Value| HashableValue::from(value);
+        let mut map = HashMap::new();
+        map.insert(key(Value::from(1)), "int");
+        map.insert(key(Value::from(1.0)), "float");
+        map.insert(key(Value::from("1")), "string");
+        map.insert(
+            key(Value::from(vec![Value::from(1), Value::from(true)])),
+            "tuple",
+        );
+        map.insert(key(Value::Empty), "empty");
+        map.insert(key(Value::from(0.0)), "zero");
+
+        assert_eq!(map.get(&key(Value::from(1))), Some(&"int"));
+        assert_eq!(map.get(&key(Value::from(1.0))), Some(&"float"));
+        assert_eq!(map.get(&key(Value::from("1"))), Some(&"string"));
+        assert_eq!(
+            map.get(&key(Value::from(vec![Value::from(1), Value::from(true)]))),
+            Some(&"tuple")
+        );
+        assert_eq!(
+            map.get(&key(Value::from(vec![Value::from(true), Value::from(1)]))),
+            None
+        );
+        assert_eq!(map.get(&key(Value::Empty)), Some(&"empty"));
+        assert_eq!(map.get(&key(Value::from(-0.0))), Some(&"zero"));
+
+        // Unlike with `==`, `NaN` is equal to itself, also within tuples
+        let nan = Value::from(FloatType::NAN);
+        assert_eq!(key(nan.clone()), key(nan.clone()));
+        map.insert(key(nan.clone()), "nan");
+        assert_eq!(map.get(&key(nan.clone())), Some(&"nan"));
+        let nan_tuple = Value::from(vec![nan.clone(), Value::from(-0.0)]);
+        map.insert(key(nan_tuple), "nan tuple");
+        assert_eq!(
+            map.get(&key(Value::from(vec![nan, Value::from(0.0)]))),
+            Some(&"nan tuple")
+        );
+
+        let set: HashSet<_> = vec![Value::from(2), Value::from(2), Value::from(2.0)]
+            .into_iter()
+            .map(HashableValue::from)
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_value_from_str() {
+        assert_eq!("42".parse(), Ok(Value::Int(42)));
+        assert_eq!(" -42 ".parse(), Ok(Value::Int(-42)));
+        assert_eq!("3.5".parse(), Ok(Value::Float(3.5)));
+        assert_eq!("-3.5".parse(), Ok(Value::Float(-3.5)));
+        assert_eq!("true".parse(), Ok(Value::Boolean(true)));
+        assert_eq!("\"hi\"".parse(), Ok(Value::from("hi")));
+        assert_eq!("\"a\\\"b\"".parse(), Ok(Value::from("a\"b")));
+        assert_eq!("()".parse(), Ok(Value::Empty));
+
+        for string in &[
+            "1 + 2", "", "x", "hi", "(1, 2)", "--1", "-true", "(1)", "f()",
+        ] {
+            assert_eq!(
+                string.parse::<Value>(),
+                Err(EvalexprError::CustomMessage(format!(
+                    "Expected a single literal value, but got {:?}",
+                    string
+                )))
+            );
+        }
+        assert_eq!(
+            "\"\\x\"".parse::<Value>(),
+            Err(EvalexprError::IllegalEscapeSequence("\\x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_value_as_tuple_iter() {
+        let tuple = Value::from(vec![Value::from(1), Value::from("b"), Value::Empty]);
+        let elements: Vec<&Value> = tuple.as_tuple_iter().unwrap().collect();
+        assert_eq!(
+            elements,
+            vec![&Value::from(1), &Value::from("b"), &Value::Empty]
+        );
+        assert_eq!(
+            Value::from(TupleType::new())
+                .as_tuple_iter()
+                .unwrap()
+                .count(),
+            0
+        );
+
+        assert_eq!(
+            Value::from(3).as_tuple_iter().map(Iterator::count),
+            Err(EvalexprError::expected_tuple(Value::from(3)))
+        );
+        assert_eq!(
+            Value::Empty.as_tuple_iter().map(Iterator::count),
+            Err(EvalexprError::expected_tuple(Value::Empty))
+        );
+    }
+
+    #[test]
+    fn test_value_try_into() {
+        let int: Result<IntType, _> = Value::from(5).try_into();
+        assert_eq!(int, Ok(5));
+        let float: Result<FloatType, _> = Value::from(5.5).try_into();
+        assert_eq!(float, Ok(5.5));
+
+        let int: Result<IntType, _> = Value::from("5").try_into();
+        assert_eq!(
+            int,
+            Err(EvalexprError::ExpectedInt {
+                actual: Value::from("5")
+            })
+        );
+        let float: Result<FloatType, _> = Value::from("5.5").try_into();
+        assert_eq!(
+            float,
+            Err(EvalexprError::ExpectedFloat {
+                actual: Value::from("5.5")
+            })
+        );
+    }
+
+    #[test]
+    fn test_value_into_conversions() {
+        assert_eq!(
+            Value::from("string").into_string(),
+            Ok(String::from("string"))
+        );
+        assert_eq!(Value::from(3).into_int(), Ok(3));
+        assert_eq!(Value::from(3.3).into_float(), Ok(3.3));
+        assert_eq!(Value::from(true).into_boolean(), Ok(true));
+        assert_eq!(
+            Value::from(TupleType::new()).into_tuple(),
+            Ok(TupleType::new())
+        );
+
+        assert_eq!(
+            Value::from(3).into_string(),
+            Err(EvalexprError::expected_string(Value::from(3)))
+        );
+        assert_eq!(
+            Value::from("3").into_int(),
+            Err(EvalexprError::expected_int(Value::from("3")))
+        );
+        assert_eq!(
+            Value::from(3).into_float(),
+            Err(EvalexprError::expected_float(Value::from(3)))
+        );
+        assert_eq!(
+            Value::from(3).into_boolean(),
+            Err(EvalexprError::expected_boolean(Value::from(3)))
+        );
+        assert_eq!(
+            Value::from(3).into_tuple(),
+            Err(EvalexprError::expected_tuple(Value::from(3)))
+        );
+    }
+
+    #[test]
+    fn test_value_into_conversions_do_not_clone() {
+        let tuple: TupleType = (0..1000).map(Value::from).collect();
+        let tuple_pointer = tuple.as_ptr();
+        let tuple = Value::from(tuple).into_tuple().unwrap();
+        assert_eq!(tuple.as_ptr(), tuple_pointer);
+
+        let string = "a".repeat(1000);
+        let string_pointer = string.as_ptr();
+        let string = Value::from(string).into_string().unwrap();
+        assert_eq!(string.as_ptr(), string_pointer);
+    }
 }
@@ -0,0 +1,139 @@
+use crate::value::{FloatType, IntType, Value};
+use std::cmp::Ordering;
+
+/// Values are ordered as follows:
+///
+/// * Numbers are compared by their numeric value, also between integers and floats.
+///   Integers are compared exactly, without converting them to floats.
+///   Since an integer is never equal to a float, an integer is ordered before a float with the same numeric value.
+///   `NaN` is not comparable to any number.
+/// * Strings are compared lexicographically by their bytes.
+/// * Booleans are ordered with `false` before `true`.
+/// * Tuples are compared lexicographically by their elements.
+/// * Empty values are equal to each other.
+///
+/// Values of different categories, like a string and a number, are not comparable.
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Boolean(a), Value::Boolean(b)) => a.partial_cmp(b),
+            (Value::Tuple(a), Value::Tuple(b)) => a.partial_cmp(b),
+            (Value::Empty, Value::Empty) => Some(Ordering::Equal),
+            (a, b) => compare_numbers(a, b).map(|ordering| {
+                ordering.then_with(|| match (a, b) {
+                    (Value::Int(_), Value::Float(_)) => Ordering::Less,
+                    (Value::Float(_), Value::Int(_)) => Ordering::Greater,
+                    _ => Ordering::Equal,
+                })
+            }),
+        }
+    }
+}
+
+/// Compares two numbers by their numeric value, or returns `None` if one of them is NaN or not a number.
+/// Unlike `Value::partial_cmp`, an integer and a float with the same numeric value are equal.
+pub(crate) fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+        (Value::Int(a), Value::Float(b)) => compare_int_float(*a, *b),
+        (Value::Float(a), Value::Int(b)) => compare_int_float(*b, *a).map(Ordering::reverse),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        _ => None,
+    }
+}
+
+/// Compares an integer with a float exactly, without converting the integer to a float.
+/// Returns `None` if the float is NaN.
+fn compare_int_float(int: IntType, float: FloatType) -> Option<Ordering> {
+    // `IntType::MIN` is a power of two and hence exactly representable as `FloatType`.
+    let bound = -(IntType::MIN as FloatType);
+    if float.is_nan() {
+        None
+    } else if float >= bound {
+        Some(Ordering::Less)
+    } else if float < -bound {
+        Some(Ordering::Greater)
+    } else {
+        let truncated = float.trunc();
+        Some(int.cmp(&(truncated as IntType)).then_with(|| {
+            // The integer parts are equal, so the fractional part of the float decides
+            if float > truncated {
+                Ordering::Less
+            } else if float < truncated {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::{FloatType, IntType, Value};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_value_ordering() {
+        // Numbers compare numerically across integers and floats
+        assert!(Value::from(1) < Value::from(1.5));
+        assert!(Value::from(2) > Value::from(1.5));
+        assert!(Value::from(-3.5) < Value::from(-3));
+        assert!(Value::from(9007199254740993) > Value::from(9007199254740992.0));
+        assert!(Value::from(IntType::MAX) < Value::from(IntType::MAX as FloatType));
+        assert_eq!(
+            Value::from(1).partial_cmp(&Value::from(1.0)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::from(1.0).partial_cmp(&Value::from(1)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            Value::from(0.0).partial_cmp(&Value::from(-0.0)),
+            Some(Ordering::Equal)
+        );
+
+        assert!(Value::from("abc") < Value::from("abd"));
+        assert!(Value::from(false) < Value::from(true));
+        assert!(
+            Value::from(vec![Value::from(1), Value::from("b")])
+                < Value::from(vec![Value::from(1), Value::from("c")])
+        );
+        assert_eq!(
+            Value::Empty.partial_cmp(&Value::Empty),
+            Some(Ordering::Equal)
+        );
+
+        // Incomparable pairs
+        assert_eq!(Value::from("1").partial_cmp(&Value::from(1)), None);
+        assert_eq!(Value::from(true).partial_cmp(&Value::from(1)), None);
+        assert_eq!(Value::Empty.partial_cmp(&Value::from(0)), None);
+        assert_eq!(
+            Value::from(FloatType::NAN).partial_cmp(&Value::from(1)),
+            None
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1)]).partial_cmp(&Value::from(vec![Value::from("1")])),
+            None
+        );
+
+        let mut values = vec![
+            Value::from(2.5),
+            Value::from(1),
+            Value::from(-4),
+            Value::from(2),
+        ];
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            values,
+            vec![
+                Value::from(-4),
+                Value::from(1),
+                Value::from(2),
+                Value::from(2.5)
+            ]
+        );
+    }
+}
@@ -391,10 +391,115 @@ fn test_builtin_functions() {
     assert_eq!(eval("typeof(\"\")"), Ok(Value::String("string".into())));
     assert_eq!(eval("typeof(true)"), Ok(Value::String("boolean".into())));
     assert_eq!(eval("typeof()"), Ok(Value::String("empty".into())));
+    assert_eq!(eval("is_int(3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_int(3.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_float(3.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_float(3)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_number(3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_number(3.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_number(\"3\")"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_string(\"3\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_string(3)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_boolean(false)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_boolean(0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_tuple(1, 2)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_tuple(1)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("is_empty()"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_empty(())"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("is_empty(\"\")"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("xor(false, false)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("xor(false, true)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("xor(true, false)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("xor(true, true)"), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval("xor(true, 1)"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval("xor(true)"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Boolean(true)
+        })
+    );
     assert_eq!(eval("min(4.0, 3)"), Ok(Value::Int(3)));
     assert_eq!(eval("max(4.0, 3)"), Ok(Value::Float(4.0)));
+    assert_eq!(eval("min(4)"), Ok(Value::Int(4)));
+    // Integers are compared exactly, even if their float conversions are equal
+    assert_eq!(
+        eval("min(9007199254740993, 9007199254740992)"),
+        Ok(Value::Int(9007199254740992))
+    );
+    assert_eq!(
+        eval("max(9007199254740992, 9007199254740993)"),
+        Ok(Value::Int(9007199254740993))
+    );
+    assert_eq!(
+        eval("max(9007199254740993, 9007199254740992.0)"),
+        Ok(Value::Int(9007199254740993))
+    );
+    assert_eq!(
+        eval("min(9007199254740993, 9007199254740994.0)"),
+        Ok(Value::Int(9007199254740993))
+    );
+    assert_eq!(
+        eval(&format!("max({}, {}.0)", IntType::MAX, IntType::MAX)),
+        Ok(Value::Float(IntType::MAX as FloatType))
+    );
+    assert_eq!(
+        eval_with_context(
+            "min(x, -1.5)",
+            &context_map! { "x" => IntType::MIN }.unwrap()
+        ),
+        Ok(Value::Int(IntType::MIN))
+    );
+    assert_eq!(eval("min(-0.5, 0)"), Ok(Value::Float(-0.5)));
+    assert_eq!(eval("max(2, 2.5, 1)"), Ok(Value::Float(2.5)));
+    assert_eq!(eval("min(3, 3.0)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("max(math::sqrt(-1), 1)"), Ok(Value::Int(1)));
+    assert_eq!(eval("max(4.5)"), Ok(Value::Float(4.5)));
+    assert_eq!(
+        eval("min()"),
+        Err(EvalexprError::CustomMessage(
+            "min expected at least one argument".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("max()"),
+        Err(EvalexprError::CustomMessage(
+            "max expected at least one argument".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("max(slice((1, 2), 0, 0))"),
+        Err(EvalexprError::CustomMessage(
+            "max expected at least one argument".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("min(slice((1, 2), 1, 1))"),
+        Err(EvalexprError::CustomMessage(
+            "min expected at least one argument".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("min(\"a\")"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::from("a")
+        })
+    );
     assert_eq!(eval("len(\"foobar\")"), Ok(Value::Int(6)));
     assert_eq!(eval("len(\"a\", \"b\")"), Ok(Value::Int(2)));
+    assert_eq!(eval("len(\"héllo\")"), Ok(Value::Int(6)));
+    assert_eq!(eval("str::char_count(\"héllo\")"), Ok(Value::Int(5)));
+    assert_eq!(eval("str::char_count(\"\")"), Ok(Value::Int(0)));
+    assert_eq!(
+        eval("str::char_count((\"a\", \"b\"))"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::from(vec![Value::from("a"), Value::from("b")])
+        })
+    );
     // String
     assert_eq!(
         eval("str::to_lowercase(\"FOOBAR\")"),
@@ -412,7 +517,10 @@ fn test_builtin_functions() {
         eval("str::from(\"a\")"),
         Ok(Value::String(String::from("\"a\"")))
     );
-    assert_eq!(eval("str::from(1.0)"), Ok(Value::String(String::from("1"))));
+    assert_eq!(
+        eval("str::from(1.0)"),
+        Ok(Value::String(String::from("1.0")))
+    );
     assert_eq!(eval("str::from(1)"), Ok(Value::String(String::from("1"))));
     assert_eq!(
         eval("str::from(true)"),
@@ -471,6 +579,7 @@ fn test_errors() {
 }
 
 #[test]
+#[allow(clippy::legacy_numeric_constants)]
 fn test_no_panic() {
     assert!(eval(&format!(
         "{} + {}",
@@ -589,6 +698,13 @@ fn test_shortcut_functions() {
     );
 
     assert_eq!(eval_int("3"), Ok(3));
+    assert_eq!(eval_int("2 + 3"), Ok(5));
+    assert_eq!(
+        eval_int("\"x\""),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::from("x")
+        })
+    );
     assert_eq!(
         eval_int("3.3"),
         Err(EvalexprError::ExpectedInt {
@@ -1607,9 +1723,9 @@ fn test_error_constructors() {
     );
     assert_eq!(
         eval("&"),
-        Err(EvalexprError::UnmatchedPartialToken {
-            first: PartialToken::Ampersand,
-            second: None
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 0
         })
     );
 
@@ -1648,6 +1764,18 @@ fn test_iterators() {
     let mut iter = tree.iter_function_identifiers();
     assert_eq!(iter.next(), Some("fun"));
     assert_eq!(iter.next(), None);
+
+    let tree = build_operator_tree("a + b * a").unwrap();
+    let identifiers: Vec<_> = tree.iter_variable_identifiers().collect();
+    assert_eq!(identifiers, vec!["a", "b", "a"]);
+
+    let tree = build_operator_tree("math::sqrt(x) + my_function(y, math::sqrt 4)").unwrap();
+    let identifiers: Vec<_> = tree.iter_function_identifiers().collect();
+    assert_eq!(identifiers, vec!["math::sqrt", "my_function", "math::sqrt"]);
+    let allowed = ["math::sqrt", "my_function"];
+    assert!(tree
+        .iter_function_identifiers()
+        .all(|identifier| allowed.contains(&identifier)));
 }
 
 #[test]
@@ -2144,3 +2272,2967 @@ fn test_variable_assignment_and_iteration() {
     variables.sort_unstable();
     assert_eq!(variables, vec!["a".to_string(), "b".to_string()],);
 }
+
+#[test]
+fn test_str_split() {
+    assert_eq!(
+        eval("str::split(\"a,b,c\", \",\")"),
+        Ok(Value::from(vec![
+            Value::from("a"),
+            Value::from("b"),
+            Value::from("c")
+        ]))
+    );
+    assert_eq!(
+        eval("str::split(\"a::b::\", \"::\")"),
+        Ok(Value::from(vec![
+            Value::from("a"),
+            Value::from("b"),
+            Value::from("")
+        ]))
+    );
+    assert_eq!(
+        eval("str::split(\"abc\", \",\")"),
+        Ok(Value::from(vec![Value::from("abc")]))
+    );
+    assert_eq!(
+        eval("str::split(\"abc\", 1)"),
+        Err(EvalexprError::expected_string(Value::from(1)))
+    );
+    assert_eq!(
+        eval("str::split(1, \",\")"),
+        Err(EvalexprError::expected_string(Value::from(1)))
+    );
+}
+
+#[test]
+fn test_str_join() {
+    let context = context_map! {
+        "single" => Value::from(vec![Value::from("a")]),
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval("str::join((\"a\", \"b\", \"c\"), \", \")"),
+        Ok(Value::from("a, b, c"))
+    );
+    assert_eq!(
+        eval_with_context("str::join(single, \",\")", &context),
+        Ok(Value::from("a"))
+    );
+    assert_eq!(
+        eval_with_context("str::join(empty, \",\")", &context),
+        Ok(Value::from(""))
+    );
+    assert_eq!(eval("str::join((), \",\")"), Ok(Value::from("")));
+    assert_eq!(
+        eval("str::join((\"a\", 2, \"c\"), \",\")"),
+        Err(EvalexprError::expected_string(Value::from(2)))
+    );
+}
+
+#[test]
+fn test_str_predicates() {
+    assert_eq!(
+        eval("str::contains(\"foobar\", \"oba\")"),
+        Ok(Value::from(true))
+    );
+    assert_eq!(
+        eval("str::contains(\"foobar\", \"baz\")"),
+        Ok(Value::from(false))
+    );
+    assert_eq!(
+        eval("str::starts_with(\"foobar\", \"foo\")"),
+        Ok(Value::from(true))
+    );
+    assert_eq!(
+        eval("str::starts_with(\"foobar\", \"bar\")"),
+        Ok(Value::from(false))
+    );
+    assert_eq!(
+        eval("str::ends_with(\"foobar\", \"bar\")"),
+        Ok(Value::from(true))
+    );
+    assert_eq!(
+        eval("str::ends_with(\"foobar\", \"foo\")"),
+        Ok(Value::from(false))
+    );
+    assert_eq!(
+        eval("str::contains(\"foobar\", 1)"),
+        Err(EvalexprError::expected_string(Value::from(1)))
+    );
+    assert_eq!(
+        eval("str::starts_with(1, \"foo\")"),
+        Err(EvalexprError::expected_string(Value::from(1)))
+    );
+}
+
+#[test]
+fn test_str_index_of() {
+    assert_eq!(
+        eval("str::index_of(\"hello world\", \"world\")"),
+        Ok(Value::Int(6))
+    );
+    assert_eq!(eval("str::index_of(\"abcabc\", \"c\")"), Ok(Value::Int(2)));
+    assert_eq!(eval("str::index_of(\"abc\", \"\")"), Ok(Value::Int(0)));
+    assert_eq!(
+        eval("str::index_of(\"hello\", \"world\")"),
+        Ok(Value::Int(-1))
+    );
+    // The index counts characters, not bytes
+    assert_eq!(eval("str::index_of(\"héllo\", \"llo\")"), Ok(Value::Int(2)));
+    assert_eq!(
+        eval("str::char_at(\"héllo\", str::index_of(\"héllo\", \"l\"))"),
+        Ok(Value::from("l"))
+    );
+    assert_eq!(
+        eval("str::index_of(\"abc\", 1)"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Int(1)
+        })
+    );
+}
+
+#[test]
+fn test_str_replace() {
+    assert_eq!(
+        eval("str::replace(\"foobar\", \"o\", \"0\")"),
+        Ok(Value::from("f00bar"))
+    );
+    assert_eq!(
+        eval("str::replace(\"abc\", \"\", \"-\")"),
+        Ok(Value::from("-a-b-c-"))
+    );
+    assert_eq!(
+        eval("str::replace(\"foobar\", \"baz\", \"qux\")"),
+        Ok(Value::from("foobar"))
+    );
+    assert_eq!(
+        eval("str::replace(\"foobar\", \"o\")"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            3,
+            Value::from(vec![Value::from("foobar"), Value::from("o")])
+        ))
+    );
+}
+
+#[test]
+fn test_str_substring() {
+    assert_eq!(
+        eval("str::substring(\"foobar\", 1, 3)"),
+        Ok(Value::from("oob"))
+    );
+    assert_eq!(
+        eval("str::substring(\"foobar\", 0, 0)"),
+        Ok(Value::from(""))
+    );
+    assert_eq!(
+        eval("str::substring(\"grüße\", 2, 2)"),
+        Ok(Value::from("üß"))
+    );
+    assert_eq!(
+        eval("str::substring(\"foobar\", 3, 100)"),
+        Ok(Value::from("bar"))
+    );
+    assert_eq!(
+        eval("str::substring(\"foobar\", 100, 1)"),
+        Ok(Value::from(""))
+    );
+    assert_eq!(
+        eval("str::substring(\"foobar\", -1, 1)"),
+        Err(EvalexprError::CustomMessage(
+            "str::substring expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::substring(\"foobar\", 1.0, 1)"),
+        Err(EvalexprError::expected_int(Value::from(1.0)))
+    );
+}
+
+#[test]
+fn test_str_char_at() {
+    assert_eq!(eval("str::char_at(\"foobar\", 0)"), Ok(Value::from("f")));
+    assert_eq!(eval("str::char_at(\"foobar\", 5)"), Ok(Value::from("r")));
+    assert_eq!(eval("str::char_at(\"a😀b\", 1)"), Ok(Value::from("😀")));
+    assert_eq!(eval("str::char_at(\"a😀b\", 2)"), Ok(Value::from("b")));
+    assert_eq!(
+        eval("str::char_at(\"a😀b\", 3)"),
+        Err(EvalexprError::CustomMessage(
+            "str::char_at index 3 is out of bounds for a string of 3 characters".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::char_at(\"foobar\", -1)"),
+        Err(EvalexprError::CustomMessage(
+            "str::char_at expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_str_repeat() {
+    assert_eq!(eval("str::repeat(\"ab\", 3)"), Ok(Value::from("ababab")));
+    assert_eq!(eval("str::repeat(\"ab\", 0)"), Ok(Value::from("")));
+    assert_eq!(
+        eval("str::repeat(\"ab\", -1)"),
+        Err(EvalexprError::CustomMessage(
+            "str::repeat expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+    assert!(matches!(
+        eval(&format!("str::repeat(\"ab\", {})", IntType::MAX)),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert!(matches!(
+        eval("str::repeat(\"ab\", 1000000000000)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+
+    let mut context = HashMapContext::new();
+    context.set_max_repeated_string_length(4);
+    assert_eq!(
+        eval_with_context("str::repeat(\"ab\", 2)", &context),
+        Ok(Value::from("abab"))
+    );
+    assert_eq!(
+        eval_with_context("str::repeat(\"ab\", 3)", &context),
+        Err(EvalexprError::CustomMessage(
+            "str::repeat result would exceed the maximum string length of 4 bytes".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("str::pad_left(\"ab\", 5, \" \")", &context),
+        Err(EvalexprError::CustomMessage(
+            "str::pad_left result would exceed the maximum string length of 4 bytes".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_str_format() {
+    assert_eq!(
+        eval("str::format(\"{} + {} = {}\", 1, 2, 3)"),
+        Ok(Value::from("1 + 2 = 3"))
+    );
+    assert_eq!(
+        eval("str::format(\"{}: {}, {}, {}\", \"values\", 2.5, true, (1, \"a\"))"),
+        Ok(Value::from("values: 2.5, true, (1, \"a\")"))
+    );
+    assert_eq!(eval("str::format(\"{{{}}}\", 1)"), Ok(Value::from("{1}")));
+    assert_eq!(eval("str::format(\"plain\")"), Ok(Value::from("plain")));
+    assert_eq!(
+        eval("str::format(\"{} + {} = {}\", 1, 2)"),
+        Err(EvalexprError::CustomMessage(
+            "str::format expected 3 arguments for the placeholders in \"{} + {} = {}\", but got 2"
+                .to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::format(\"{}\", 1, 2)"),
+        Err(EvalexprError::CustomMessage(
+            "str::format expected 1 arguments for the placeholders in \"{}\", but got 2"
+                .to_string()
+        ))
+    );
+    assert!(matches!(
+        eval("str::format(\"{0}\", 1)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert_eq!(
+        eval("str::format(1, 2)"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Int(1)
+        })
+    );
+}
+
+#[test]
+fn test_str_pad() {
+    assert_eq!(
+        eval("str::pad_left(\"7\", 3, \"0\")"),
+        Ok(Value::from("007"))
+    );
+    assert_eq!(
+        eval("str::pad_right(\"ab\", 5, \".\")"),
+        Ok(Value::from("ab..."))
+    );
+    assert_eq!(
+        eval("str::pad_left(\"long\", 2, \" \")"),
+        Ok(Value::from("long"))
+    );
+    assert_eq!(
+        eval("str::pad_right(\"long\", 4, \" \")"),
+        Ok(Value::from("long"))
+    );
+    // The width counts characters, not bytes
+    assert_eq!(
+        eval("str::pad_left(\"é\", 3, \"ü\")"),
+        Ok(Value::from("üüé"))
+    );
+    assert_eq!(
+        eval("str::pad_right(\"x\", 2, \"ab\")"),
+        Err(EvalexprError::CustomMessage(
+            "str::pad_right expected a single character to fill with, but got \"ab\"".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::pad_left(\"x\", 2, \"\")"),
+        Err(EvalexprError::CustomMessage(
+            "str::pad_left expected a single character to fill with, but got \"\"".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::pad_left(\"x\", -1, \" \")"),
+        Err(EvalexprError::CustomMessage(
+            "str::pad_left expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+    assert!(matches!(
+        eval(&format!("str::pad_right(\"x\", {}, \" \")", IntType::MAX)),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+}
+
+#[test]
+fn test_negation_overflow() {
+    assert_eq!(eval("-5"), Ok(Value::Int(-5)));
+    assert_eq!(eval("-(-5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("-(2.5)"), Ok(Value::Float(-2.5)));
+    assert_eq!(
+        eval(&format!("-({})", IntType::MAX)),
+        Ok(Value::Int(-IntType::MAX))
+    );
+
+    let context = context_map! { "min" => IntType::MIN }.unwrap();
+    assert_eq!(
+        eval_with_context("-min", &context),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+    assert_eq!(
+        eval(&format!("-({} - 1)", -IntType::MAX)),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+
+    // The integer overflow mode only applies to binary operators
+    let mut context = context_map! { "min" => IntType::MIN }.unwrap();
+    context.set_integer_overflow_mode(IntegerOverflowMode::Wrapping);
+    assert_eq!(
+        eval_with_context("-min", &context),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+}
+
+#[test]
+fn test_math_abs() {
+    assert_eq!(eval("math::abs(5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("math::abs(-5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("math::abs(-5.5)"), Ok(Value::Float(5.5)));
+    assert_eq!(eval("math::abs(0.0)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval(&format!("math::abs({} - 1)", -IntType::MAX)),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+    assert_eq!(
+        eval("math::abs(\"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_math_gcd_lcm() {
+    assert_eq!(eval("math::gcd(12, 18)"), Ok(Value::Int(6)));
+    assert_eq!(eval("math::gcd(-12, 18)"), Ok(Value::Int(6)));
+    assert_eq!(eval("math::gcd(7, 0)"), Ok(Value::Int(7)));
+    assert_eq!(eval("math::gcd(0, 0)"), Ok(Value::Int(0)));
+    assert!(matches!(
+        eval(&format!("math::gcd({} - 1, 0)", -IntType::MAX)),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert_eq!(eval("math::lcm(4, 6)"), Ok(Value::Int(12)));
+    assert_eq!(eval("math::lcm(-4, 6)"), Ok(Value::Int(12)));
+    assert_eq!(eval("math::lcm(0, 6)"), Ok(Value::Int(0)));
+    assert_eq!(
+        eval(&format!(
+            "math::lcm({}, {})",
+            IntType::MAX,
+            IntType::MAX - 1
+        )),
+        Err(EvalexprError::MultiplicationError {
+            multiplicand: Value::Int(IntType::MAX),
+            multiplier: Value::Int(IntType::MAX - 1)
+        })
+    );
+    assert_eq!(
+        eval("math::gcd(4.0, 6)"),
+        Err(EvalexprError::expected_int(Value::Float(4.0)))
+    );
+}
+
+#[test]
+fn test_math_signum() {
+    assert_eq!(eval("math::signum(5)"), Ok(Value::Int(1)));
+    assert_eq!(eval("math::signum(-5)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("math::signum(0)"), Ok(Value::Int(0)));
+    assert_eq!(eval("math::signum(5.5)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::signum(-5.5)"), Ok(Value::Float(-1.0)));
+    assert_eq!(eval("math::signum(0.0)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::signum(-0.0)"), Ok(Value::Float(-1.0)));
+    assert_eq!(
+        eval("math::is_nan(math::signum(0.0 / 0.0))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("math::signum(true)"),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+}
+
+#[test]
+fn test_math_clamp() {
+    assert_eq!(eval("math::clamp(-5, 0, 10)"), Ok(Value::Int(0)));
+    assert_eq!(eval("math::clamp(5, 0, 10)"), Ok(Value::Int(5)));
+    assert_eq!(eval("math::clamp(15, 0, 10)"), Ok(Value::Int(10)));
+    assert_eq!(eval("math::clamp(-0.5, 0.0, 1.0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::clamp(0.5, 0.0, 1.0)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("math::clamp(1.5, 0, 1)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::clamp(5, 0.5, 1)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::clamp(5, 0.0, 10.0)"), Ok(Value::Int(5)));
+    assert_eq!(eval("math::clamp(0.5, 0, 1)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("math::clamp(-5, 0.0, 10.0)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval("math::is_nan(math::clamp(math::sqrt(-1), 0.0, 1.0))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("math::clamp(5, 10, 0)"),
+        Err(EvalexprError::CustomMessage(
+            "math::clamp expected min <= max, but got min = 10 and max = 0".to_string()
+        ))
+    );
+    assert!(matches!(
+        eval("math::clamp(5.0, 1.5, 0.5)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert_eq!(
+        eval("math::clamp(\"a\", 0, 1)"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_math_angle_conversion() {
+    let radians = eval_float("math::to_radians(180)").unwrap();
+    assert!((radians - std::f64::consts::PI).abs() < 1e-12);
+    let degrees = eval_float("math::to_degrees(math::to_radians(180))").unwrap();
+    assert!((degrees - 180.0).abs() < 1e-12);
+    assert_eq!(eval("math::to_degrees(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval("math::to_radians(\"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_math_sum() {
+    let context = context_map! {
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(eval("math::sum(1, 2, 3)"), Ok(Value::Int(6)));
+    assert_eq!(eval("math::sum(1, 2.5, 3)"), Ok(Value::Float(6.5)));
+    assert_eq!(
+        eval_with_context("math::sum(empty)", &context),
+        Ok(Value::Int(0))
+    );
+    assert_eq!(eval("math::sum(())"), Ok(Value::Int(0)));
+    assert_eq!(
+        eval(&format!("math::sum({}, 1)", IntType::MAX)),
+        Err(EvalexprError::AdditionError {
+            augend: Value::Int(IntType::MAX),
+            addend: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval("math::sum(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_math_product() {
+    let context = context_map! {
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(eval("math::product(2, 3, 4)"), Ok(Value::Int(24)));
+    assert_eq!(eval("math::product(2, 1.5, 4)"), Ok(Value::Float(12.0)));
+    assert_eq!(
+        eval_with_context("math::product(empty)", &context),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(eval("math::product(())"), Ok(Value::Int(1)));
+    assert_eq!(
+        eval(&format!("math::product({}, 2)", IntType::MAX)),
+        Err(EvalexprError::MultiplicationError {
+            multiplicand: Value::Int(IntType::MAX),
+            multiplier: Value::Int(2)
+        })
+    );
+    assert_eq!(
+        eval("math::product(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_math_mean_median() {
+    let context = context_map! {
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(eval("math::mean(1, 2, 6)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::mean(1, 2.5, 3, 4.5)"), Ok(Value::Float(2.75)));
+    assert_eq!(eval("math::median(6, 1, 2)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("math::median(4.5, 1, 3, 2.5)"), Ok(Value::Float(2.75)));
+    assert_eq!(
+        eval_with_context("math::mean(empty)", &context),
+        Err(EvalexprError::CustomMessage(
+            "math::mean expected a non-empty tuple".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("math::median(empty)", &context),
+        Err(EvalexprError::CustomMessage(
+            "math::median expected a non-empty tuple".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("math::median(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+    assert_eq!(
+        eval("math::median(3.0, math::sqrt(-1), 1, 2, 5.0, 4)"),
+        Err(EvalexprError::CustomMessage(
+            "math::median expected numbers that are not NaN, but got (3.0, NaN, 1, 2, 5.0, 4)"
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_math_round_to() {
+    assert_eq!(eval("math::round_to(2.71828, 2)"), Ok(Value::Float(2.72)));
+    assert_eq!(eval("math::round_to(2.5, 0)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::round_to(1234.5, -1)"), Ok(Value::Float(1230.0)));
+    assert_eq!(eval("math::round_to(1250, -2)"), Ok(Value::Float(1300.0)));
+    assert_eq!(
+        eval("math::round_to(-1.23456, 3)"),
+        Ok(Value::Float(-1.235))
+    );
+    assert_eq!(
+        eval("math::round_to(2.71828, 2.0)"),
+        Err(EvalexprError::expected_int(Value::Float(2.0)))
+    );
+
+    // Powers of ten that do not fit into a float
+    assert_eq!(eval("math::round_to(1.5, 400)"), Ok(Value::Float(1.5)));
+    assert_eq!(
+        eval(&format!("math::round_to(1.5, {})", IntType::MAX)),
+        Ok(Value::Float(1.5))
+    );
+    assert_eq!(eval("math::round_to(1e300, 100)"), Ok(Value::Float(1e300)));
+    assert_eq!(eval("math::round_to(1.5, -400)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval_with_context(
+            "math::round_to(1.5, digits)",
+            &context_map! { "digits" => IntType::MIN }.unwrap()
+        ),
+        Ok(Value::Float(0.0))
+    );
+    let negative_zero = eval_float("math::round_to(-1.5, -400)").unwrap();
+    assert!(negative_zero == 0.0 && negative_zero.is_sign_negative());
+    assert_eq!(
+        eval("math::round_to(math::sqrt(-1), -400)")
+            .unwrap()
+            .as_float()
+            .map(FloatType::is_nan),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_math_trunc_fract() {
+    assert_eq!(eval("math::trunc(3.7)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::trunc(-3.7)"), Ok(Value::Float(-3.0)));
+    assert_eq!(eval("math::trunc(3)"), Ok(Value::Float(3.0)));
+    let fract = eval_float("math::fract(3.7)").unwrap();
+    assert!((fract - 0.7).abs() < 1e-12);
+    let fract = eval_float("math::fract(-3.7)").unwrap();
+    assert!((fract + 0.7).abs() < 1e-12);
+    assert_eq!(
+        eval("math::fract(\"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+}
+
+#[test]
+fn test_numeric_conversions() {
+    assert_eq!(eval("to_int(5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("to_int(5.7)"), Ok(Value::Int(5)));
+    assert_eq!(eval("to_int(-5.7)"), Ok(Value::Int(-5)));
+    assert_eq!(eval("to_int(\"-42\")"), Ok(Value::Int(-42)));
+    assert_eq!(
+        eval("to_int(\"4.2\")"),
+        Err(EvalexprError::CustomMessage(
+            "Cannot parse \"4.2\" as an integer".to_string()
+        ))
+    );
+    assert!(matches!(
+        eval("to_int(1e100)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert!(matches!(
+        eval("to_int(0.0 / 0.0)"),
+        Err(EvalexprError::CustomMessage(_))
+    ));
+    assert_eq!(
+        eval("to_int(true)"),
+        Err(EvalexprError::type_error(
+            Value::Boolean(true),
+            vec![ValueType::Int, ValueType::Float, ValueType::String]
+        ))
+    );
+
+    assert_eq!(eval("to_float(5)"), Ok(Value::Float(5.0)));
+    assert_eq!(eval("to_float(5.5)"), Ok(Value::Float(5.5)));
+    assert_eq!(eval("to_float(\"-4.25\")"), Ok(Value::Float(-4.25)));
+    assert_eq!(
+        eval("to_float(\"abc\")"),
+        Err(EvalexprError::CustomMessage(
+            "Cannot parse \"abc\" as a float".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_str_parse() {
+    assert_eq!(eval("str::parse_int(\"42\")"), Ok(Value::Int(42)));
+    assert_eq!(eval("str::parse_float(\"2.5\")"), Ok(Value::Float(2.5)));
+    assert_eq!(eval("str::parse_float(\"42\")"), Ok(Value::Float(42.0)));
+    assert_eq!(
+        eval("str::parse_int(\"abc\")"),
+        Err(EvalexprError::CustomMessage(
+            "Cannot parse \"abc\" as an integer".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::parse_float(\"abc\")"),
+        Err(EvalexprError::CustomMessage(
+            "Cannot parse \"abc\" as a float".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("str::parse_int(42)"),
+        Err(EvalexprError::expected_string(Value::Int(42)))
+    );
+}
+
+#[test]
+fn test_tuple_get() {
+    assert_eq!(eval("get((1, \"b\", 3.0), 0)"), Ok(Value::Int(1)));
+    assert_eq!(eval("get((1, \"b\", 3.0), 1)"), Ok(Value::from("b")));
+    assert_eq!(eval("get((1, \"b\", 3.0), 2)"), Ok(Value::Float(3.0)));
+    assert_eq!(
+        eval("get((1, \"b\", 3.0), 3)"),
+        Err(EvalexprError::CustomMessage(
+            "get index 3 is out of bounds for a tuple of length 3".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("get((1, \"b\", 3.0), -1)"),
+        Err(EvalexprError::CustomMessage(
+            "get expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("get(1, 0)"),
+        Err(EvalexprError::expected_tuple(Value::Int(1)))
+    );
+}
+
+#[test]
+fn test_tuple_contains() {
+    let context = context_map! {
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval("contains((1, \"b\", 3.0), \"b\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains((1, \"b\", 3.0), 3.0)"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains((1, \"b\", 3.0), 3)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("contains((1, (2, 3)), (2, 3))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval_with_context("contains(empty, 1)", &context),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("contains(1, 1)"),
+        Err(EvalexprError::expected_tuple(Value::Int(1)))
+    );
+}
+
+#[test]
+fn test_tuple_slice() {
+    assert_eq!(
+        eval("slice((1, 2, 3, 4), 1, 3)"),
+        Ok(Value::Tuple(vec![Value::Int(2), Value::Int(3)]))
+    );
+    assert_eq!(
+        eval("slice((1, 2, 3, 4), 0, 4)"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(3),
+            Value::Int(4)
+        ]))
+    );
+    assert_eq!(eval("slice((1, 2, 3, 4), 2, 2)"), Ok(Value::Tuple(vec![])));
+    assert_eq!(
+        eval("slice((1, 2, 3, 4), 3, 1)"),
+        Err(EvalexprError::CustomMessage(
+            "slice bounds 3..1 are invalid for a tuple of length 4".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("slice((1, 2, 3, 4), 1, 5)"),
+        Err(EvalexprError::CustomMessage(
+            "slice bounds 1..5 are invalid for a tuple of length 4".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("slice((1, 2, 3, 4), -1, 2)"),
+        Err(EvalexprError::CustomMessage(
+            "slice expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_tuple_concat() {
+    let context = context_map! {
+        "empty" => Value::from(TupleType::new()),
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval("concat((1, 2), (3, 4))"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(3),
+            Value::Int(4)
+        ]))
+    );
+    assert_eq!(
+        eval_with_context("concat(empty, (1, 2), empty)", &context),
+        Ok(Value::Tuple(vec![Value::Int(1), Value::Int(2)]))
+    );
+    assert_eq!(
+        eval("concat((1, \"a\"), (2.5, (true, false)))"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::from("a"),
+            Value::Float(2.5),
+            Value::Tuple(vec![Value::Boolean(true), Value::Boolean(false)])
+        ]))
+    );
+    assert_eq!(
+        eval("concat((1, 2), 3)"),
+        Err(EvalexprError::expected_tuple(Value::Int(3)))
+    );
+}
+
+#[test]
+fn test_reverse() {
+    assert_eq!(
+        eval("reverse(1, \"b\", 3.0)"),
+        Ok(Value::Tuple(vec![
+            Value::Float(3.0),
+            Value::from("b"),
+            Value::Int(1)
+        ]))
+    );
+    assert_eq!(eval("reverse(\"abc\")"), Ok(Value::from("cba")));
+    assert_eq!(eval("reverse(\"\")"), Ok(Value::from("")));
+    assert_eq!(eval("reverse(\"grüße\")"), Ok(Value::from("eßürg")));
+    assert_eq!(
+        eval("reverse(1)"),
+        Err(EvalexprError::type_error(
+            Value::Int(1),
+            vec![ValueType::String, ValueType::Tuple]
+        ))
+    );
+}
+
+#[test]
+fn test_tuple_sort() {
+    assert_eq!(
+        eval("sort(3, 1, 2)"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(3)
+        ]))
+    );
+    assert_eq!(
+        eval("sort(2.5, -1.0, 0.5)"),
+        Ok(Value::Tuple(vec![
+            Value::Float(-1.0),
+            Value::Float(0.5),
+            Value::Float(2.5)
+        ]))
+    );
+    assert_eq!(
+        eval("sort(2.5, 1, 2)"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Float(2.5)
+        ]))
+    );
+    assert_eq!(
+        eval("sort(\"pear\", \"apple\", \"fig\")"),
+        Ok(Value::Tuple(vec![
+            Value::from("apple"),
+            Value::from("fig"),
+            Value::from("pear")
+        ]))
+    );
+    assert_eq!(
+        eval("sort(3.0, math::sqrt(-1), 1, 2, 5.0, 4)"),
+        Err(EvalexprError::CustomMessage(
+            "sort expected numbers that are not NaN, but got (3.0, NaN, 1, 2, 5.0, 4)".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("sort(1, \"a\")"),
+        Err(EvalexprError::CustomMessage(
+            "sort expected a tuple of only numbers or only strings, but got (1, \"a\")".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_tuple_range() {
+    assert_eq!(
+        eval("range(0, 3)"),
+        Ok(Value::Tuple(vec![
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(2)
+        ]))
+    );
+    assert_eq!(
+        eval("range(-2, 0)"),
+        Ok(Value::Tuple(vec![Value::Int(-2), Value::Int(-1)]))
+    );
+    assert_eq!(eval("range(3, 3)"), Ok(Value::Tuple(vec![])));
+    assert_eq!(eval("range(3, 0)"), Ok(Value::Tuple(vec![])));
+    assert_eq!(
+        eval("range(0, 1000000000000)"),
+        Err(EvalexprError::CustomMessage(
+            "range result would exceed the maximum tuple length of 16777216 elements".to_string()
+        ))
+    );
+    assert_eq!(
+        eval(&format!("range(-{}, {})", IntType::MAX, IntType::MAX)),
+        Err(EvalexprError::CustomMessage(
+            "range result would exceed the maximum tuple length of 16777216 elements".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("range(0, 1.5)"),
+        Err(EvalexprError::expected_int(Value::Float(1.5)))
+    );
+
+    let mut context = HashMapContext::new();
+    context.set_max_range_length(2);
+    assert_eq!(
+        eval_with_context("range(0, 2)", &context),
+        Ok(Value::Tuple(vec![Value::Int(0), Value::Int(1)]))
+    );
+    assert_eq!(
+        eval_with_context("range(0, 3)", &ReadOnlyContext::new(&context)),
+        Err(EvalexprError::CustomMessage(
+            "range result would exceed the maximum tuple length of 2 elements".to_string()
+        ))
+    );
+    context.set_max_range_length(0);
+    assert_eq!(
+        eval_with_context("range(3, 3)", &context),
+        Ok(Value::Tuple(vec![]))
+    );
+}
+
+#[test]
+fn test_function_new_fixed() {
+    let context = context_map! {
+        "sub" => Function::new_fixed(2, |arguments| {
+            Ok(Value::from(arguments[0].as_int()? - arguments[1].as_int()?))
+        }),
+        "first" => Function::new_fixed(1, |arguments| Ok(arguments[0].clone())),
+        "zero" => Function::new_fixed(0, |_| Ok(Value::from(0))),
+    }
+    .unwrap();
+
+    assert_eq!(eval_with_context("sub(5, 3)", &context), Ok(Value::from(2)));
+    assert_eq!(
+        eval_with_context("sub(5)", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 2,
+            actual: 1
+        })
+    );
+    assert_eq!(
+        eval_with_context("sub(5, 3, 1)", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 2,
+            actual: 3
+        })
+    );
+    assert_eq!(
+        eval_with_context("sub()", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 2,
+            actual: 0
+        })
+    );
+    assert_eq!(
+        eval_with_context("first(1, 2)", &context),
+        Ok(Value::Tuple(vec![Value::from(1), Value::from(2)]))
+    );
+    assert_eq!(eval_with_context("zero()", &context), Ok(Value::from(0)));
+    assert_eq!(
+        eval_with_context("zero(1)", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 0,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn test_function_new_with_context() {
+    let mut context = context_map! {
+        "scale" => 3,
+        "scaled" => Function::new_with_context(|context, argument| {
+            match context.get_value("scale") {
+                Some(scale) => Ok(Value::from(argument.as_int()? * scale.as_int()?)),
+                None => Err(EvalexprError::VariableIdentifierNotFound("scale".to_string())),
+            }
+        }),
+        "call_twice" => Function::new_with_context(|context, argument| {
+            let identifier = argument.as_string()?;
+            let once = context.call_function(&identifier, &Value::from(1))?;
+            context.call_function(&identifier, &once)
+        }),
+    }
+    .unwrap();
+
+    assert_eq!(eval_with_context("scaled(2)", &context), Ok(Value::from(6)));
+    assert_eq!(
+        eval_with_context("call_twice(\"scaled\")", &context),
+        Ok(Value::from(9))
+    );
+    assert_eq!(
+        eval_with_context_mut("scale = 5; scaled(2)", &mut context),
+        Ok(Value::from(10))
+    );
+}
+
+#[test]
+fn test_function_new_with_context_through_wrapper() {
+    let functions = context_map! {
+        "scaled" => Function::new_with_context(|context, argument| {
+            Ok(Value::from(argument.as_int()? * context.get_int("scale")?))
+        }),
+    }
+    .unwrap();
+
+    // The function sees the wrapper it is called through, not only the context that contains it
+    let chained = ChainedContext::new(context_map! { "scale" => 3 }.unwrap(), &functions);
+    assert_eq!(eval_with_context("scaled(2)", &chained), Ok(Value::from(6)));
+    assert_eq!(
+        eval_with_context("scaled(2)", &ReadOnlyContext::new(&chained)),
+        Ok(Value::from(6))
+    );
+    assert_eq!(
+        eval_with_context("map((1, 2), \"scaled\")", &chained),
+        Ok(Value::from(vec![Value::from(3), Value::from(6)]))
+    );
+
+    let defaulted = DefaultContext::new(&functions, Value::from(4));
+    assert_eq!(
+        eval_with_context("scaled(2)", &defaulted),
+        Ok(Value::from(8))
+    );
+    assert_eq!(
+        eval_with_context("SCALED(2)", &CaseInsensitiveContext::new(&defaulted)),
+        Ok(Value::from(8))
+    );
+
+    assert_eq!(
+        eval_with_context("scaled(2)", &functions),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "scale".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_node_display_round_trip() {
+    let expressions = [
+        "1 + 2 * 3",
+        "(1 + 2) * 3",
+        "1 - (2 - 3)",
+        "1 - 2 - 3",
+        "2 ^ 3 ^ 4",
+        "2 ^ (3 ^ 4)",
+        "-2 ^ 2",
+        "(-2) ^ 2",
+        "- -2",
+        "!(true && false) || !!true",
+        "a = b = 3; a + b",
+        "x += 2; x",
+        "1, (2, 3), ((4))",
+        "f(a, b), g(), h 5, i j 6",
+        "math::sqrt(4.0) + 1.5e-7 * 2.0",
+        "\"a \\\"quoted\\\" \\\\ string\" + str::from(1)",
+        "(a < b) == (c >= d)",
+        "(a; b), c",
+        "",
+    ];
+
+    for expression in &expressions {
+        let tree = build_operator_tree(expression).unwrap();
+        let string = tree.to_string();
+        assert_eq!(
+            build_operator_tree(&string),
+            Ok(tree),
+            "{:?} was displayed as {:?}",
+            expression,
+            string
+        );
+    }
+
+    assert_eq!(
+        build_operator_tree("(1+2)*-3^2").unwrap().to_string(),
+        "(1 + 2) * -3 ^ 2"
+    );
+    assert_eq!(
+        build_operator_tree("a=(1,2.0,\"s\");f(a)")
+            .unwrap()
+            .to_string(),
+        "a = (1, 2.0, \"s\"); f(a)"
+    );
+
+    // Negative constants cannot be parsed, but can be inserted manually
+    let mut tree = build_operator_tree("2 ^ x").unwrap();
+    *tree.children_mut()[0].children_mut()[1].operator_mut() = Operator::Const {
+        value: Value::Int(-1),
+    };
+    assert_eq!(tree.to_string(), "2 ^ (-1)");
+    assert_eq!(
+        build_operator_tree(&tree.to_string()).unwrap().eval(),
+        tree.eval()
+    );
+}
+
+#[test]
+fn test_value_display() {
+    let values = [
+        Value::from("string"),
+        Value::from("with \"quotes\" and \\backslashes\\"),
+        Value::Float(3.0),
+        Value::Float(-0.25),
+        Value::Float(1.5e-10),
+        Value::Float(2e300),
+        Value::Int(-42),
+        Value::Int(IntType::MIN),
+        Value::Int(IntType::MAX),
+        Value::Boolean(false),
+        Value::Empty,
+    ];
+    for value in &values {
+        assert_eq!(eval(&value.to_string()).as_ref(), Ok(value));
+    }
+
+    assert_eq!(Value::from("a\"b").to_string(), "\"a\\\"b\"");
+    assert_eq!(Value::Float(3.0).to_string(), "3.0");
+    assert_eq!(Value::Int(3).to_string(), "3");
+    assert_eq!(
+        Value::Int(IntType::MIN).to_string(),
+        "(-9223372036854775807 - 1)"
+    );
+    assert_eq!(Value::Boolean(true).to_string(), "true");
+    assert_eq!(Value::Empty.to_string(), "()");
+
+    let tuple = Value::Tuple(vec![
+        Value::Int(1),
+        Value::Tuple(vec![Value::Float(2.0), Value::from("three")]),
+        Value::Empty,
+        Value::Tuple(vec![Value::Int(4)]),
+    ]);
+    assert_eq!(tuple.to_string(), "(1, (2.0, \"three\"), (), (4,))");
+    assert_eq!(eval(&tuple.to_string()), Ok(tuple));
+}
+
+#[test]
+fn test_node_iter_post_order() {
+    let tree = build_operator_tree("a + b * c").unwrap();
+    let pre_order: Vec<_> = tree.iter().map(|node| node.operator().clone()).collect();
+    let post_order: Vec<_> = tree
+        .iter_post_order()
+        .map(|node| node.operator().clone())
+        .collect();
+    let (a, b, c) = (
+        Operator::VariableIdentifierRead {
+            identifier: "a".to_string(),
+        },
+        Operator::VariableIdentifierRead {
+            identifier: "b".to_string(),
+        },
+        Operator::VariableIdentifierRead {
+            identifier: "c".to_string(),
+        },
+    );
+
+    assert_eq!(
+        pre_order,
+        vec![
+            Operator::Add,
+            a.clone(),
+            Operator::Mul,
+            b.clone(),
+            c.clone()
+        ]
+    );
+    assert_eq!(post_order, vec![a, b, c, Operator::Mul, Operator::Add]);
+
+    let tree = build_operator_tree("").unwrap();
+    assert_eq!(tree.iter_post_order().count(), 0);
+    let tree = build_operator_tree("f(1, 2)").unwrap();
+    assert_eq!(tree.iter_post_order().count(), tree.iter().count());
+}
+
+#[test]
+fn test_node_iter_mut() {
+    let mut tree = build_operator_tree("a + b * f(a)").unwrap();
+    let mut nodes = tree.iter_mut();
+    while let Some(node) = nodes.next() {
+        match node.operator_mut() {
+            operator @ Operator::Add => *operator = Operator::Sub,
+            Operator::VariableIdentifierRead { identifier } if identifier == "a" => {
+                *identifier = "x".to_string()
+            },
+            Operator::FunctionIdentifier { identifier } => *identifier = "g".to_string(),
+            _ => (),
+        }
+    }
+
+    let context = context_map! {
+        "x" => 10,
+        "b" => 2,
+        "g" => Function::new(|argument| Ok(Value::from(argument.as_int()? + 1))),
+    }
+    .unwrap();
+    assert_eq!(tree.to_string(), "x - b * g(x)");
+    assert_eq!(tree.eval_with_context(&context), Ok(Value::from(-12)));
+
+    let mut count = 0;
+    let mut nodes = tree.iter_mut();
+    while nodes.next().is_some() {
+        count += 1;
+    }
+    assert!(nodes.next().is_none());
+    assert_eq!(count, tree.iter().count());
+
+    // Replaced subtrees are traversed with their new children
+    let mut tree = build_operator_tree("a * b").unwrap();
+    let mut visited = Vec::new();
+    let mut nodes = tree.iter_mut();
+    while let Some(node) = nodes.next() {
+        visited.push(node.operator().clone());
+        if node.operator()
+            == &(Operator::VariableIdentifierRead {
+                identifier: "a".to_string(),
+            })
+        {
+            *node = build_operator_tree("c + 1").unwrap();
+        }
+    }
+    assert_eq!(tree.to_string(), "(c + 1) * b");
+    // The replaced node is visited before and its new children after the replacement
+    assert_eq!(visited.len(), tree.iter().count());
+    assert_eq!(
+        tree.eval_with_context(&context_map! { "b" => 3, "c" => 4 }.unwrap()),
+        Ok(Value::from(15))
+    );
+}
+
+#[test]
+fn test_node_len_and_depth() {
+    let empty = build_operator_tree("").unwrap();
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.depth(), 0);
+
+    let single = build_operator_tree("1").unwrap();
+    assert_eq!(single.len(), 1);
+    assert_eq!(single.depth(), 1);
+
+    // Root -> Tuple -> Root -> Const
+    let flat = build_operator_tree("1, 2, 3, 4, 5, 6").unwrap();
+    assert_eq!(flat.len(), 13);
+    assert_eq!(flat.depth(), 3);
+
+    // Root -> (Neg -> Root)* -> Const
+    let nested = build_operator_tree("-(-(-(-(-(-1)))))").unwrap();
+    assert_eq!(nested.len(), 12);
+    assert_eq!(nested.depth(), 12);
+}
+
+#[test]
+fn test_node_fold_constants() {
+    let context = context_map! {
+        "x" => 4,
+        "f" => Function::new(|argument| Ok(Value::from(argument.as_int()? * 10))),
+    }
+    .unwrap();
+    let expressions = [
+        "2 * 3 + x",
+        "x + 2 * 3",
+        "(1 + 2) * (x - (4 - 1))",
+        "f(2 * 3) + f(x)",
+        "(1, 2 + 3, x)",
+        "str::from(1 + 2)",
+        "y = 1 + 2; y * 2",
+    ];
+
+    for expression in &expressions {
+        let tree = build_operator_tree(expression).unwrap();
+        let folded = tree.fold_constants();
+        assert!(folded.len() < tree.len(), "{:?} was not folded", expression);
+        assert_eq!(
+            folded.eval_with_context_mut(&mut context.clone()),
+            tree.eval_with_context_mut(&mut context.clone())
+        );
+    }
+
+    assert_eq!(
+        build_operator_tree("2 * 3 + x")
+            .unwrap()
+            .fold_constants()
+            .to_string(),
+        "6 + x"
+    );
+    assert_eq!(
+        build_operator_tree("f(2 * 3)")
+            .unwrap()
+            .fold_constants()
+            .to_string(),
+        "f(6)"
+    );
+    assert_eq!(
+        build_operator_tree("1 / 0 + x")
+            .unwrap()
+            .fold_constants()
+            .to_string(),
+        "1 / 0 + x"
+    );
+    assert_eq!(
+        build_operator_tree("1 + 2").unwrap().fold_constants(),
+        build_operator_tree("3").unwrap().fold_constants()
+    );
+}
+
+#[test]
+fn test_node_substitute() {
+    let tree = build_operator_tree("x + y").unwrap();
+    let substituted = tree.substitute("x", Value::from(5));
+    let context = context_map! { "y" => 2 }.unwrap();
+    assert_eq!(substituted.to_string(), "5 + y");
+    assert_eq!(substituted.eval_with_context(&context), Ok(Value::from(7)));
+    assert_eq!(
+        tree.eval_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("x".to_string()))
+    );
+
+    assert_eq!(tree.substitute("z", Value::from(5)), tree);
+    assert_eq!(
+        build_operator_tree("x ^ 2")
+            .unwrap()
+            .substitute("x", Value::from(IntType::MIN))
+            .to_string(),
+        "(-9223372036854775807 - 1) ^ 2"
+    );
+
+    let tree = build_operator_tree("x = x * f(x); x").unwrap();
+    assert_eq!(
+        tree.substitute("x", Value::from(-2.0)).to_string(),
+        "x = -2.0 * f(-2.0); -2.0"
+    );
+    assert_eq!(
+        build_operator_tree("2 ^ x")
+            .unwrap()
+            .substitute("x", Value::from(-1))
+            .eval(),
+        Ok(Value::from(0.5))
+    );
+}
+
+#[test]
+fn test_saturating_integer_overflow_mode() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        context.integer_overflow_mode(),
+        IntegerOverflowMode::Checked
+    );
+    assert_eq!(
+        eval_with_context(&format!("{} + 1", IntType::MAX), &context),
+        Err(EvalexprError::AdditionError {
+            augend: Value::from(IntType::MAX),
+            addend: Value::from(1)
+        })
+    );
+
+    context.set_integer_overflow_mode(IntegerOverflowMode::Saturating);
+    assert_eq!(
+        eval_with_context(&format!("{} + 1", IntType::MAX), &context),
+        Ok(Value::from(IntType::MAX))
+    );
+    assert_eq!(
+        eval_with_context(&format!("-{} - 2", IntType::MAX), &context),
+        Ok(Value::from(IntType::MIN))
+    );
+    assert_eq!(
+        eval_with_context(&format!("{} * -2", IntType::MAX), &context),
+        Ok(Value::from(IntType::MIN))
+    );
+    assert_eq!(
+        eval_with_context_mut(&format!("a = {}; a *= 2; a", IntType::MAX), &mut context),
+        Ok(Value::from(IntType::MAX))
+    );
+    assert_eq!(
+        eval_with_context("2 + 3 * 4", &context),
+        Ok(Value::from(14))
+    );
+    assert_eq!(
+        eval_with_context(&format!("{} / 0", IntType::MAX), &context),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from(IntType::MAX),
+            divisor: Value::from(0)
+        })
+    );
+}
+
+#[test]
+fn test_wrapping_integer_overflow_mode() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context(&format!("{} + 1", IntType::MAX), &context),
+        Err(EvalexprError::AdditionError {
+            augend: Value::from(IntType::MAX),
+            addend: Value::from(1)
+        })
+    );
+
+    context.set_integer_overflow_mode(IntegerOverflowMode::Wrapping);
+    assert_eq!(
+        eval_with_context(&format!("{} + 1", IntType::MAX), &context),
+        Ok(Value::from(IntType::MIN))
+    );
+    assert_eq!(
+        eval_with_context(&format!("-{} - 2", IntType::MAX), &context),
+        Ok(Value::from(IntType::MAX))
+    );
+    assert_eq!(
+        eval_with_context(&format!("{} * 2", IntType::MAX), &context),
+        Ok(Value::from(-2))
+    );
+    assert_eq!(
+        eval_with_context_mut(&format!("a = {}; a += 2; a", IntType::MAX), &mut context),
+        Ok(Value::from(IntType::MIN + 1))
+    );
+    assert_eq!(
+        eval_with_context("2 + 3 * 4", &context),
+        Ok(Value::from(14))
+    );
+}
+
+#[test]
+fn test_conditional_operator() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context_mut("a = 3; b = 5; a > b ? a : b", &mut context),
+        Ok(Value::from(5))
+    );
+    assert_eq!(
+        eval_with_context("b > a ? a : b", &context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        eval("1 + 1 == 2 ? \"yes\" : \"no\""),
+        Ok(Value::from("yes"))
+    );
+    assert_eq!(eval("false || false ? 1 : 2"), Ok(Value::from(2)));
+    assert_eq!(eval("(true ? 1 : 2) + 3"), Ok(Value::from(4)));
+    assert_eq!(
+        eval("true ? (1, 2) : 3"),
+        Ok(Value::from(vec![Value::from(1), Value::from(2)]))
+    );
+
+    // Chaining in the last branch
+    assert_eq!(eval("-5 < 0 ? -1 : -5 == 0 ? 0 : 1"), Ok(Value::from(-1)));
+    assert_eq!(eval("0 < 0 ? -1 : 0 == 0 ? 0 : 1"), Ok(Value::from(0)));
+    assert_eq!(eval("5 < 0 ? -1 : 5 == 0 ? 0 : 1"), Ok(Value::from(1)));
+    assert_eq!(eval("true ? (false ? 1 : 2) : 3"), Ok(Value::from(2)));
+
+    // The branch that is not taken is not evaluated
+    assert_eq!(eval("true ? 1 : 1 / 0"), Ok(Value::from(1)));
+    assert_eq!(eval("false ? unknown : 2"), Ok(Value::from(2)));
+    assert_eq!(
+        eval_with_context_mut("c = 0; true ? (c = 1) : (c = 2); c", &mut context),
+        Ok(Value::from(1))
+    );
+    assert_eq!(
+        eval_with_context_mut("c = 0; false ? (c = 1) : (c = 2); c", &mut context),
+        Ok(Value::from(2))
+    );
+
+    // Comparisons bind stronger than the conditional
+    let tree = build_operator_tree("a > b ? a : b").unwrap();
+    assert_eq!(tree.to_string(), "a > b ? a : b");
+    assert_eq!(
+        tree.children()[0].children()[0].children()[0].operator(),
+        &Operator::Gt
+    );
+    assert_eq!(
+        build_operator_tree("true ? (false ? 1 : 2) : 3")
+            .unwrap()
+            .to_string(),
+        "true ? (false ? 1 : 2) : 3"
+    );
+    assert_eq!(
+        build_operator_tree("x = c ? 1 : 2").unwrap().to_string(),
+        "x = c ? 1 : 2"
+    );
+
+    assert_eq!(
+        eval("1 ? 2 : 3"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::from(1)
+        })
+    );
+    assert_eq!(eval("true ? 1"), Err(EvalexprError::UnmatchedConditional));
+    assert_eq!(eval("true : 1"), Err(EvalexprError::UnmatchedConditional));
+    assert_eq!(
+        eval("true ? false ? 1 : 2 : 3"),
+        Err(EvalexprError::UnmatchedConditional)
+    );
+    assert_eq!(
+        eval("(true ? 1) : 2"),
+        Err(EvalexprError::UnmatchedConditional)
+    );
+    assert_eq!(eval("str::from(true ? 1 : 2)"), Ok(Value::from("1")));
+}
+
+#[test]
+fn test_null_coalescing_operator() {
+    let mut context = context_map! {
+        "empty_var" => Value::Empty,
+        "three" => 3,
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval_with_context("empty_var ?? 5", &context),
+        Ok(Value::from(5))
+    );
+    assert_eq!(eval_with_context("3 ?? 5", &context), Ok(Value::from(3)));
+    assert_eq!(
+        eval_with_context("three ?? 5", &context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(eval("() ?? ()"), Ok(Value::Empty));
+    assert_eq!(eval("false ?? true"), Ok(Value::from(false)));
+    assert_eq!(
+        eval_with_context("empty_var ?? empty_var ?? \"default\"", &context),
+        Ok(Value::from("default"))
+    );
+    assert_eq!(
+        eval_with_context("(empty_var ?? 2) * 3", &context),
+        Ok(Value::from(6))
+    );
+    assert_eq!(
+        eval_with_context("empty_var ?? 2 == 2 ? \"two\" : \"other\"", &context),
+        Ok(Value::from("two"))
+    );
+
+    // The right side is only evaluated if the left side is empty
+    assert_eq!(
+        eval_with_context("three ?? 1 / 0", &context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        eval_with_context("empty_var ?? 1 / 0", &context),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from(1),
+            divisor: Value::from(0)
+        })
+    );
+    assert_eq!(
+        eval_with_context_mut("a = 0; three ?? (a = 1); a", &mut context),
+        Ok(Value::from(0))
+    );
+    assert_eq!(
+        eval_with_context_mut("a = 0; empty_var ?? (a = 1); a", &mut context),
+        Ok(Value::from(1))
+    );
+    assert_eq!(
+        eval_with_context("unknown ?? 5", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "unknown".to_string()
+        ))
+    );
+
+    assert_eq!(
+        build_operator_tree("a ?? b ?? c").unwrap().to_string(),
+        "a ?? b ?? c"
+    );
+    assert_eq!(
+        build_operator_tree("a ?? (b ?? c)").unwrap().to_string(),
+        "a ?? (b ?? c)"
+    );
+}
+
+#[test]
+fn test_string_concatenation() {
+    assert_eq!(eval("\"foo\" + \"bar\""), Ok(Value::from("foobar")));
+    assert_eq!(eval("\"\" + \"\""), Ok(Value::from("")));
+    assert_eq!(eval("\"a\" + \"b\" + \"c\""), Ok(Value::from("abc")));
+    assert_eq!(eval("\"foo\" + str::from(1)"), Ok(Value::from("foo1")));
+    assert_eq!(
+        eval_with_context_mut("s = \"foo\"; s += \"bar\"; s", &mut HashMapContext::new()),
+        Ok(Value::from("foobar"))
+    );
+
+    assert_eq!(
+        eval("\"foo\" + 1"),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::Add,
+            actual: vec![ValueType::String, ValueType::Int]
+        })
+    );
+    assert_eq!(
+        eval("1.5 + \"foo\""),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::Add,
+            actual: vec![ValueType::Float, ValueType::String]
+        })
+    );
+    assert_eq!(
+        eval("\"foo\" + true"),
+        Err(EvalexprError::ExpectedNumberOrString {
+            actual: Value::from(true)
+        })
+    );
+    assert_eq!(
+        eval("\"foo\" - \"bar\""),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::from("foo")
+        })
+    );
+}
+
+#[test]
+fn test_floor_division() {
+    assert_eq!(eval("7 // 2"), Ok(Value::from(3)));
+    assert_eq!(eval("7 // 2 == 3"), Ok(Value::from(true)));
+    assert_eq!(eval("6 // 2"), Ok(Value::from(3)));
+    assert_eq!(eval("-7 // 2"), Ok(Value::from(-4)));
+    assert_eq!(eval("7 // -2"), Ok(Value::from(-4)));
+    assert_eq!(eval("-7 // -2"), Ok(Value::from(3)));
+    assert_eq!(eval("-6 // 2"), Ok(Value::from(-3)));
+    assert_eq!(eval("1 + 7 // 2 * 2"), Ok(Value::from(7)));
+
+    // Floats are truncated
+    assert_eq!(eval("7.0 // 2"), Ok(Value::from(3.0)));
+    assert_eq!(eval("-7 // 2.0"), Ok(Value::from(-3.0)));
+    assert_eq!(eval("7.5 // 0.5"), Ok(Value::from(15.0)));
+
+    assert_eq!(
+        eval("7 // 0"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from(7),
+            divisor: Value::from(0)
+        })
+    );
+    assert_eq!(
+        eval_with_context("a // -1", &context_map! { "a" => IntType::MIN }.unwrap()),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::from(IntType::MIN),
+            divisor: Value::from(-1)
+        })
+    );
+    assert_eq!(
+        eval("\"7\" // 2"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::from("7")
+        })
+    );
+    assert_eq!(
+        build_operator_tree("a // b / c").unwrap().to_string(),
+        "a // b / c"
+    );
+}
+
+#[test]
+fn test_double_star_exponentiation() {
+    assert_eq!(eval("2 ** 3"), Ok(Value::from(8.0)));
+    assert_eq!(eval("2 ** 3 == 8.0"), Ok(Value::from(true)));
+    assert_eq!(eval("2**0.5"), eval("2^0.5"));
+    assert_eq!(eval("3 * 2 ** 2"), Ok(Value::from(12.0)));
+    assert_eq!(eval("2 ** 2 * 3"), Ok(Value::from(12.0)));
+    assert_eq!(eval("-2 ** 2"), eval("-2 ^ 2"));
+    assert_eq!(eval("2 ** 3 ^ 2"), eval("2 ^ 3 ^ 2"));
+    assert_eq!(
+        build_operator_tree("3 * 2 ** 2").unwrap(),
+        build_operator_tree("3 * 2 ^ 2").unwrap()
+    );
+    assert_eq!(
+        build_operator_tree("2 ** 3 ** 2").unwrap(),
+        build_operator_tree("2 ^ 3 ^ 2").unwrap()
+    );
+    assert_eq!(build_operator_tree("2 ** 3").unwrap().to_string(), "2 ^ 3");
+    assert!(eval("2 *** 3").is_err());
+}
+
+#[test]
+fn test_radix_int_literals() {
+    assert_eq!(eval("0xFF"), Ok(Value::from(255)));
+    assert_eq!(eval("0xff == 255"), Ok(Value::from(true)));
+    assert_eq!(eval("0o17 == 15"), Ok(Value::from(true)));
+    assert_eq!(eval("0b1010 == 10"), Ok(Value::from(true)));
+    assert_eq!(eval("0x0"), Ok(Value::from(0)));
+    assert_eq!(eval("-0x10"), Ok(Value::from(-16)));
+    assert_eq!(eval("0b11 + 0o7 * 0x2"), Ok(Value::from(17)));
+    assert_eq!(eval("0x7FFFFFFFFFFFFFFF"), Ok(Value::from(IntType::MAX)));
+
+    assert_eq!(
+        eval("0x8000000000000000"),
+        Err(EvalexprError::IntLiteralOutOfRange(
+            "0x8000000000000000".to_string()
+        ))
+    );
+    assert_eq!(
+        eval(&format!("0b1{}", "0".repeat(64))),
+        Err(EvalexprError::IntLiteralOutOfRange(format!(
+            "0b1{}",
+            "0".repeat(64)
+        )))
+    );
+
+    // Literals without valid digits are identifiers
+    assert_eq!(
+        eval("0xZZ"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "0xZZ".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("0b12"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "0b12".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("0x"),
+        Err(EvalexprError::VariableIdentifierNotFound("0x".to_string()))
+    );
+}
+
+#[test]
+fn test_digit_separators() {
+    assert_eq!(eval("1_000_000"), Ok(Value::from(1000000)));
+    assert_eq!(eval("1_0"), Ok(Value::from(10)));
+    assert_eq!(eval("1.234_567"), Ok(Value::from(1.234567)));
+    assert_eq!(eval("1_000.000_1"), Ok(Value::from(1000.0001)));
+    assert_eq!(eval("1_0e1_0"), Ok(Value::from(10e10)));
+    assert_eq!(eval("1_0e-1_0"), Ok(Value::from(10e-10)));
+    assert_eq!(eval("-1_000 + 1"), Ok(Value::from(-999)));
+    assert_eq!(eval("0xFF_FF"), Ok(Value::from(0xFFFF)));
+    assert_eq!(eval("0b1010_1010"), Ok(Value::from(0b1010_1010)));
+
+    for malformed in &[
+        "1__0", "5_", "1_.5", "1._5", "1_e5", "1e_5", "1_e-5", "1e-5_", "0x_FF", "0xFF_", "0b1__0",
+    ] {
+        assert_eq!(
+            eval(malformed),
+            Err(EvalexprError::MisplacedDigitSeparator(
+                malformed.to_string()
+            )),
+            "{} should be rejected as a malformed number",
+            malformed
+        );
+    }
+    assert_eq!(
+        eval("2 + 1__0"),
+        Err(EvalexprError::MisplacedDigitSeparator("1__0".to_string()))
+    );
+
+    // Like in Rust, a leading underscore makes an identifier
+    assert_eq!(
+        eval("_5"),
+        Err(EvalexprError::VariableIdentifierNotFound("_5".to_string()))
+    );
+
+    // Identifiers can still contain underscores
+    let context = context_map! { "a_1" => 1, "_" => 2 }.unwrap();
+    assert_eq!(eval_with_context("a_1 + _", &context), Ok(Value::from(3)));
+}
+
+#[test]
+fn test_line_comments() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context_mut("a = 1;\n# a = 2;\nb = a + 1; # b = 5;\na + b", &mut context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(context.get_value("b"), Some(&Value::from(2)));
+
+    assert_eq!(eval("1 + # comment\n2"), Ok(Value::from(3)));
+    assert_eq!(eval("1 + 2 # comment"), Ok(Value::from(3)));
+    assert_eq!(eval("# only a comment"), Ok(Value::Empty));
+    assert_eq!(
+        eval("1#comment\n#another comment\r\n+1"),
+        Ok(Value::from(2))
+    );
+    assert_eq!(eval("\"# in a string\""), Ok(Value::from("# in a string")));
+    assert_eq!(eval("7 // 2 # floor division"), Ok(Value::from(3)));
+    assert_eq!(eval("# 1 + 1\n"), Ok(Value::Empty));
+    assert_eq!(eval("(1, # first\n 2) # second"), eval("(1, 2)"));
+}
+
+#[test]
+fn test_string_escape_sequences() {
+    assert_eq!(eval(r#""a\nb""#), Ok(Value::from("a\nb")));
+    assert_eq!(eval(r#""a\tb""#), Ok(Value::from("a\tb")));
+    assert_eq!(eval(r#""a\r\nb""#), Ok(Value::from("a\r\nb")));
+    assert_eq!(eval(r#""a\\b""#), Ok(Value::from("a\\b")));
+    assert_eq!(eval(r#""a\"b""#), Ok(Value::from("a\"b")));
+    assert_eq!(eval(r#""\u{41}""#), Ok(Value::from("A")));
+    assert_eq!(eval(r#""\u{e9}\u{00E9}""#), Ok(Value::from("éé")));
+    assert_eq!(eval(r#""\u{1F600}!""#), Ok(Value::from("\u{1F600}!")));
+    assert_eq!(eval(r#""\u{10FFFF}""#), Ok(Value::from("\u{10FFFF}")));
+    assert_eq!(eval(r#"len("\n\t")"#), Ok(Value::from(2)));
+
+    let illegal = |sequence: &str| Err(EvalexprError::IllegalEscapeSequence(sequence.to_string()));
+    assert_eq!(eval(r#""\a""#), illegal(r"\a"));
+    assert_eq!(eval(r#""\x41""#), illegal(r"\x"));
+    assert_eq!(eval(r#""\u41""#), illegal(r"\u"));
+    assert_eq!(eval(r#""\u{}""#), illegal(r"\u{}"));
+    assert_eq!(eval(r#""\u{+41}""#), illegal(r"\u{+41}"));
+    assert_eq!(eval(r#""\u{D800}""#), illegal(r"\u{D800}"));
+    assert_eq!(eval(r#""\u{110000}""#), illegal(r"\u{110000}"));
+    assert_eq!(eval(r#""\u{1000000}""#), illegal("\\u{1000000"));
+    assert_eq!(eval(r#""\u{41""#), illegal("\\u{41\""));
+    assert_eq!(eval(r#""\"#), illegal(r"\"));
+}
+
+#[test]
+fn test_chained_comparison() {
+    assert_eq!(eval("1 < 2 < 3"), Err(EvalexprError::ChainedComparison));
+    assert_eq!(eval("3 > 2 > 1"), Err(EvalexprError::ChainedComparison));
+    assert_eq!(
+        eval("1 == 1 == true"),
+        Err(EvalexprError::ChainedComparison)
+    );
+    assert_eq!(
+        eval("1 <= 2 != false"),
+        Err(EvalexprError::ChainedComparison)
+    );
+    assert_eq!(
+        build_operator_tree("1 < x < 10"),
+        Err(EvalexprError::ChainedComparison)
+    );
+    assert_eq!(eval("a = 1 < 2 < 3"), Err(EvalexprError::ChainedComparison));
+
+    assert_eq!(eval("1 < 2 && 2 < 3"), Ok(Value::from(true)));
+    assert_eq!(
+        eval("(1 < 2) < 3"),
+        Err(EvalexprError::ExpectedNumberOrString {
+            actual: Value::from(true)
+        })
+    );
+    assert_eq!(eval("(1 < 2) == (2 < 3)"), Ok(Value::from(true)));
+    assert_eq!(
+        eval("1 < 2 == (2 < 3)"),
+        Err(EvalexprError::ChainedComparison)
+    );
+    assert_eq!(eval("1 + 1 == 2"), Ok(Value::from(true)));
+    assert_eq!(eval("1 < 2 ? 3 < 4 : false"), Ok(Value::from(true)));
+}
+
+#[test]
+fn test_tokenize() {
+    assert_eq!(
+        tokenize("x = max(a, 2.5) >= 3 && !done; \"s\" # comment"),
+        Ok(vec![
+            Token::Identifier("x".to_string()),
+            Token::Assign,
+            Token::Identifier("max".to_string()),
+            Token::LBrace,
+            Token::Identifier("a".to_string()),
+            Token::Comma,
+            Token::Float(2.5),
+            Token::RBrace,
+            Token::Geq,
+            Token::Int(3),
+            Token::And,
+            Token::Not,
+            Token::Identifier("done".to_string()),
+            Token::Semicolon,
+            Token::String("s".to_string()),
+        ])
+    );
+    assert_eq!(
+        tokenize("a ?? b ? -1 : 2 ** 3 // 4"),
+        Ok(vec![
+            Token::Identifier("a".to_string()),
+            Token::Coalesce,
+            Token::Identifier("b".to_string()),
+            Token::QuestionMark,
+            Token::Minus,
+            Token::Int(1),
+            Token::Colon,
+            Token::Int(2),
+            Token::DoubleStar,
+            Token::Int(3),
+            Token::DoubleSlash,
+            Token::Int(4),
+        ])
+    );
+    assert_eq!(tokenize(""), Ok(vec![]));
+    assert_eq!(tokenize(" # only a comment"), Ok(vec![]));
+
+    // Tokenizing does not check the structure of the expression
+    assert_eq!(tokenize("(("), Ok(vec![Token::LBrace, Token::LBrace]));
+    assert_eq!(
+        tokenize("\"\\q\""),
+        Err(EvalexprError::IllegalEscapeSequence("\\q".to_string()))
+    );
+
+    // Displaying the tokens yields an equivalent expression
+    let expression = "a+=1;b=str::from(a)+\"\\\"\"";
+    let tokens = tokenize(expression).unwrap();
+    let displayed: Vec<String> = tokens.iter().map(|token| token.to_string()).collect();
+    assert_eq!(tokenize(&displayed.join(" ")), Ok(tokens));
+}
+
+#[test]
+fn test_parse_error_positions() {
+    let parse_error = |expression: &str| {
+        build_operator_tree_with_position(expression)
+            .map_err(|error| (error.position(), error.into_error()))
+    };
+
+    assert_eq!(
+        parse_error("1 + 2 + 3)"),
+        Err((9, EvalexprError::UnmatchedRBrace))
+    );
+    assert_eq!(
+        parse_error("1 + (2 + (3)"),
+        Err((4, EvalexprError::UnmatchedLBrace))
+    );
+    assert_eq!(
+        parse_error("(1, 2"),
+        Err((0, EvalexprError::UnmatchedLBrace))
+    );
+    assert_eq!(
+        parse_error("t[1"),
+        Err((1, EvalexprError::UnmatchedLBracket))
+    );
+    assert_eq!(
+        parse_error("t[(1])"),
+        Err((4, EvalexprError::UnmatchedRBracket))
+    );
+    assert_eq!(
+        parse_error("(t[1)]"),
+        Err((4, EvalexprError::UnmatchedRBrace))
+    );
+    assert_eq!(
+        parse_error("x = \"ok\"; y = \"not \\ok\""),
+        Err((14, EvalexprError::IllegalEscapeSequence("\\o".to_string())))
+    );
+    assert_eq!(
+        parse_error("1 + 0xFFFFFFFFFFFFFFFFFF"),
+        Err((
+            4,
+            EvalexprError::IntLiteralOutOfRange("0xFFFFFFFFFFFFFFFFFF".to_string())
+        ))
+    );
+    assert_eq!(
+        parse_error("2 + 1__0"),
+        Err((
+            4,
+            EvalexprError::MisplacedDigitSeparator("1__0".to_string())
+        ))
+    );
+
+    // Errors in the structure of the tree point to the offending operator
+    assert_eq!(
+        parse_error("true ? 1"),
+        Err((5, EvalexprError::UnmatchedConditional))
+    );
+    assert_eq!(
+        parse_error("true : 1"),
+        Err((5, EvalexprError::UnmatchedConditional))
+    );
+    assert_eq!(
+        parse_error("true ? false ? 1 : 2 : 3"),
+        Err((13, EvalexprError::UnmatchedConditional))
+    );
+    assert_eq!(
+        parse_error("(true ? 1) : 2"),
+        Err((11, EvalexprError::UnmatchedConditional))
+    );
+    assert_eq!(
+        parse_error("1 < 2 < 3"),
+        Err((6, EvalexprError::ChainedComparison))
+    );
+    assert_eq!(
+        parse_error("a = 1 == 1 == true"),
+        Err((11, EvalexprError::ChainedComparison))
+    );
+    assert_eq!(
+        parse_error("1 < 2 == (2 < 3)"),
+        Err((6, EvalexprError::ChainedComparison))
+    );
+
+    // Positions are byte positions
+    assert_eq!(
+        parse_error("\"äöü\" + )"),
+        Err((11, EvalexprError::UnmatchedRBrace))
+    );
+
+    let error = build_operator_tree_with_position("1 +\n(2").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Error at position 4: Found an unmatched opening parenthesis '('."
+    );
+    assert_eq!(EvalexprError::from(error), EvalexprError::UnmatchedLBrace);
+    // The other functions return the error without its position
+    assert_eq!(
+        build_operator_tree("1 +\n(2"),
+        Err(EvalexprError::UnmatchedLBrace)
+    );
+    assert_eq!(
+        build_operator_tree_with_position("1 + (2 * 3)"),
+        Ok(build_operator_tree("1 + (2 * 3)").unwrap())
+    );
+}
+
+#[test]
+fn test_iterate_variables_after_assignment() {
+    let mut context = HashMapContext::new();
+    assert_eq!(context.iter_variables().count(), 0);
+
+    eval_with_context_mut("a = 1; b = 2", &mut context).unwrap();
+    let mut variables: Vec<_> = context.iter_variables().collect();
+    variables.sort_unstable_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    assert_eq!(
+        variables,
+        vec![("a".to_string(), 1.into()), ("b".to_string(), 2.into())],
+    );
+
+    // Reassigning does not duplicate a variable, and functions are not listed
+    eval_with_context_mut("a = 3", &mut context).unwrap();
+    context
+        .set_function("f".to_string(), Function::new(|_| Ok(Value::Empty)))
+        .unwrap();
+    let mut variables: Vec<_> = context.iter_variables().collect();
+    variables.sort_unstable_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    assert_eq!(
+        variables,
+        vec![("a".to_string(), 3.into()), ("b".to_string(), 2.into())],
+    );
+
+    assert_eq!(EmptyContext.iter_variables().count(), 0);
+    assert_eq!(EmptyContext.iter_variable_names().count(), 0);
+}
+
+#[test]
+fn test_remove_and_clear_variables() {
+    let mut context = HashMapContext::new();
+    eval_with_context_mut("a = 1; b = true", &mut context).unwrap();
+    context
+        .set_function("f".to_string(), Function::new(|_| Ok(Value::Int(3))))
+        .unwrap();
+
+    assert_eq!(context.remove_variable("a"), Some(Value::Int(1)));
+    assert_eq!(context.remove_variable("a"), None);
+    assert_eq!(
+        eval_with_context("a", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("a".to_string()))
+    );
+    assert_eq!(eval_with_context("b", &context), Ok(Value::Boolean(true)));
+
+    // A removed variable can be assigned a value of another type
+    eval_with_context_mut("a = \"one\"", &mut context).unwrap();
+    assert_eq!(eval_with_context("a", &context), Ok(Value::from("one")));
+
+    context.clear_variables();
+    assert_eq!(
+        eval_with_context("b", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("b".to_string()))
+    );
+    assert_eq!(context.iter_variables().count(), 0);
+    assert_eq!(eval_with_context("f()", &context), Ok(Value::Int(3)));
+
+    assert!(context.remove_function("f").is_some());
+    assert!(context.remove_function("f").is_none());
+    assert_eq!(
+        eval_with_context("f()", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("f".to_string()))
+    );
+    // Builtin functions are not stored in the context
+    assert!(context.remove_function("min").is_none());
+    assert_eq!(eval_with_context("min(1, 2)", &context), Ok(Value::Int(1)));
+}
+
+#[test]
+fn test_extend_context() {
+    let mut context = context_map! {
+        "a" => 1,
+        "b" => 2,
+        "f" => Function::new(|_| Ok(Value::from("first"))),
+        "g" => Function::new(|_| Ok(Value::from("g"))),
+    }
+    .unwrap();
+    let other = context_map! {
+        "b" => 2.5,
+        "c" => true,
+        "f" => Function::new(|_| Ok(Value::from("second"))),
+    }
+    .unwrap();
+    context.extend(&other);
+
+    assert_eq!(eval_with_context("a", &context), Ok(Value::Int(1)));
+    assert_eq!(eval_with_context("b", &context), Ok(Value::Float(2.5)));
+    assert_eq!(eval_with_context("c", &context), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval_with_context("f()", &context),
+        Ok(Value::from("second"))
+    );
+    assert_eq!(eval_with_context("g()", &context), Ok(Value::from("g")));
+
+    // The merged context is not changed
+    assert_eq!(eval_with_context("b", &other), Ok(Value::Float(2.5)));
+    assert_eq!(
+        eval_with_context("a", &other),
+        Err(EvalexprError::VariableIdentifierNotFound("a".to_string()))
+    );
+}
+
+#[test]
+fn test_read_only_context() {
+    let mut context = ReadOnlyContext::new(
+        context_map! {
+            "x" => 4,
+            "f" => Function::new(|argument| Ok(Value::Int(argument.as_int()? * 2))),
+        }
+        .unwrap(),
+    );
+
+    assert_eq!(eval_with_context("x + 1", &context), Ok(Value::Int(5)));
+    assert_eq!(
+        eval_with_context_mut("f(x)", &mut context),
+        Ok(Value::Int(8))
+    );
+    assert_eq!(
+        eval_with_context("x = 5", &context),
+        Err(EvalexprError::ContextNotMutable)
+    );
+    for expression in &["x = 5", "x += 1", "y = 1", "x + 1; z = 2"] {
+        assert_eq!(
+            eval_with_context_mut(expression, &mut context),
+            Err(EvalexprError::ContextNotMutable)
+        );
+    }
+    assert_eq!(
+        context.set_function("g".to_string(), Function::new(|_| Ok(Value::Empty))),
+        Err(EvalexprError::ContextNotMutable)
+    );
+
+    assert_eq!(context.get_value("x"), Some(&Value::Int(4)));
+    assert_eq!(context.iter_variable_names().collect::<Vec<_>>(), vec!["x"]);
+    assert_eq!(context.into_inner().get_value("y"), None);
+}
+
+#[test]
+fn test_chained_context() {
+    let base = context_map! {
+        "a" => 1,
+        "b" => 2,
+        "f" => Function::new(|_| Ok(Value::from("base"))),
+        "g" => Function::new(|_| Ok(Value::from("g"))),
+    }
+    .unwrap();
+    let scratch = context_map! {
+        "b" => 3,
+        "f" => Function::new(|_| Ok(Value::from("scratch"))),
+    }
+    .unwrap();
+    let mut context = ChainedContext::new(scratch, &base);
+
+    // Variables only in the fallback are readable, the primary takes precedence
+    assert_eq!(eval_with_context("a", &context), Ok(Value::Int(1)));
+    assert_eq!(eval_with_context("b", &context), Ok(Value::Int(3)));
+    assert_eq!(
+        eval_with_context("f()", &context),
+        Ok(Value::from("scratch"))
+    );
+    assert_eq!(eval_with_context("g()", &context), Ok(Value::from("g")));
+    assert_eq!(
+        eval_with_context("c", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("c".to_string()))
+    );
+    assert_eq!(
+        eval_with_context("h()", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("h".to_string()))
+    );
+
+    // Writes land in the primary
+    assert_eq!(
+        eval_with_context_mut("a = 10; c = 5; a + c", &mut context),
+        Ok(Value::Int(15))
+    );
+    assert_eq!(context.primary().get_value("a"), Some(&Value::Int(10)));
+    assert_eq!(context.primary().get_value("c"), Some(&Value::Int(5)));
+    assert_eq!(base.get_value("a"), Some(&Value::Int(1)));
+    assert_eq!(base.get_value("c"), None);
+
+    // A read-only fallback still allows writes to the primary
+    let mut context = ChainedContext::new(HashMapContext::new(), ReadOnlyContext::new(&base));
+    assert_eq!(
+        eval_with_context_mut("b = b + 1; b", &mut context),
+        Ok(Value::Int(3))
+    );
+    assert_eq!(base.get_value("b"), Some(&Value::Int(2)));
+}
+
+#[test]
+fn test_default_context() {
+    let context = context_map! { "a" => 1 }.unwrap();
+    assert_eq!(
+        eval_with_context("b", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("b".to_string()))
+    );
+
+    let mut context = DefaultContext::new(context, Value::Empty);
+    assert_eq!(eval_with_context("a", &context), Ok(Value::Int(1)));
+    assert_eq!(eval_with_context("b", &context), Ok(Value::Empty));
+    assert_eq!(eval_with_context("b ?? 5", &context), Ok(Value::Int(5)));
+    assert_eq!(
+        eval_with_context("f()", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("f".to_string()))
+    );
+
+    // Assignments go to the wrapped context
+    assert_eq!(
+        eval_with_context_mut("b = 2; a + b", &mut context),
+        Ok(Value::Int(3))
+    );
+    assert_eq!(context.inner().get_value("b"), Some(&Value::Int(2)));
+
+    let mut context = DefaultContext::new(HashMapContext::new(), Value::Int(0));
+    assert_eq!(
+        eval_with_context_mut("counter += 1; counter", &mut context),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(context.default_value(), &Value::Int(0));
+}
+
+#[test]
+fn test_case_insensitive_context() {
+    let mut context = HashMapContext::new();
+    eval_with_context_mut("Temp = 20", &mut context).unwrap();
+    assert_eq!(
+        eval_with_context("temp", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "temp".to_string()
+        ))
+    );
+
+    let mut context = CaseInsensitiveContext::new(HashMapContext::new());
+    eval_with_context_mut("Temp = 20", &mut context).unwrap();
+    assert_eq!(eval_with_context("temp", &context), Ok(Value::Int(20)));
+    assert_eq!(eval_with_context("TEMP", &context), Ok(Value::Int(20)));
+    assert_eq!(
+        eval_with_context_mut("tEmP += 1; Temp", &mut context),
+        Ok(Value::Int(21))
+    );
+    assert_eq!(
+        context.iter_variable_names().collect::<Vec<_>>(),
+        vec!["temp"]
+    );
+
+    // String literals are not affected
+    assert_eq!(
+        eval_with_context_mut("Name = \"MixedCase\"; name", &mut context),
+        Ok(Value::from("MixedCase"))
+    );
+
+    context
+        .set_function(
+            "Double".to_string(),
+            Function::new(|argument| Ok(Value::Int(argument.as_int()? * 2))),
+        )
+        .unwrap();
+    assert_eq!(
+        eval_with_context("double(TEMP)", &context),
+        Ok(Value::Int(42))
+    );
+    assert_eq!(eval_with_context("DOUBLE(1)", &context), Ok(Value::Int(2)));
+    assert_eq!(
+        eval_with_context("Triple(1)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "Triple".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("Missing", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "Missing".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_typed_context_getters() {
+    let context = context_map! {
+        "s" => "string",
+        "i" => 3,
+        "f" => 2.5,
+        "b" => true,
+    }
+    .unwrap();
+
+    assert_eq!(context.get_string("s"), Ok("string".to_string()));
+    assert_eq!(context.get_int("i"), Ok(3));
+    assert_eq!(context.get_float("f"), Ok(2.5));
+    assert_eq!(context.get_boolean("b"), Ok(true));
+
+    assert_eq!(
+        context.get_int("f"),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(2.5)
+        })
+    );
+    assert_eq!(
+        context.get_float("i"),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(3)
+        })
+    );
+    assert_eq!(
+        context.get_string("b"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        context.get_boolean("missing"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "missing".to_string()
+        ))
+    );
+
+    // The getters are available for all contexts
+    let context = DefaultContext::new(EmptyContext, Value::Int(0));
+    assert_eq!(context.get_int("anything"), Ok(0));
+}
+
+#[test]
+fn test_precompiled_expression_with_many_contexts() {
+    let precompiled: Node = "if_negative(x * factor + offset)".parse().unwrap();
+    assert_eq!(
+        precompiled,
+        build_operator_tree("if_negative(x * factor + offset)").unwrap()
+    );
+    assert_eq!("(1".parse::<Node>(), Err(EvalexprError::UnmatchedLBrace));
+
+    let mut total = 0;
+    for x in -5..5 {
+        let context = context_map! {
+            "x" => x,
+            "factor" => 3,
+            "offset" => 1,
+            "if_negative" => Function::new(|argument| Ok(Value::Boolean(argument.as_int()? < 0))),
+        }
+        .unwrap();
+        if precompiled.eval_boolean_with_context(&context).unwrap() {
+            total += 1;
+        }
+    }
+    assert_eq!(total, 5);
+
+    let precompiled = build_operator_tree("x * 2").unwrap();
+    let mut context = HashMapContext::new();
+    for x in 0..10 {
+        context.set_value("x".into(), x.into()).unwrap();
+        assert_eq!(precompiled.eval_int_with_context(&context), Ok(x * 2));
+    }
+    assert_eq!(
+        precompiled.eval_float_with_context(&context_map! { "x" => 1.5 }.unwrap()),
+        Ok(3.0)
+    );
+}
+
+#[test]
+fn test_short_circuit_logical_operators() {
+    assert_eq!(
+        eval("false && undefined_var > 0"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(eval("true || undefined_var > 0"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("false && undefined_function()"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(eval("true || 1 / 0 == 0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("false && 5"), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval("true && undefined_var > 0"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "undefined_var".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("false || undefined_var > 0"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "undefined_var".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("true && 5"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(5)
+        })
+    );
+    assert_eq!(
+        eval("5 || true"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(5)
+        })
+    );
+    assert_eq!(eval("true && false || true"), Ok(Value::Boolean(true)));
+
+    // Side effects of the right operand only happen if it is evaluated
+    let mut context = context_map! { "a" => 1 }.unwrap();
+    assert_eq!(
+        eval_with_context_mut("false && (a = 2) == ()", &mut context),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::Int(1)));
+    assert_eq!(
+        eval_with_context_mut("true && (a = 2) == ()", &mut context),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::Int(2)));
+
+    let counter = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counted = counter.clone();
+    let context = context_map! {
+        "count" => Function::new(move |_| {
+            counted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(Value::Boolean(true))
+        }),
+    }
+    .unwrap();
+    assert_eq!(
+        eval_with_context("true || count() || count()", &context),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval_with_context("true && count() && count()", &context),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(counter.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_public_operator_precedence() {
+    assert!(Operator::Mul.precedence() > Operator::Add.precedence());
+    assert!(Operator::Exp.precedence() > Operator::Mul.precedence());
+    assert!(Operator::Add.precedence() > Operator::Eq.precedence());
+    assert!(Operator::And.precedence() > Operator::Or.precedence());
+    assert!(Operator::Assign.precedence() > Operator::Tuple.precedence());
+    assert!(Operator::Tuple.precedence() > Operator::Chain.precedence());
+
+    assert!(Operator::Sub.is_left_to_right());
+    assert!(!Operator::Assign.is_left_to_right());
+
+    // The parser uses the same data
+    let tree = build_operator_tree("1 + 2 * 3").unwrap();
+    assert_eq!(tree.children()[0].operator(), &Operator::Add);
+    let tree = build_operator_tree("2 * 3 ^ 2").unwrap();
+    assert_eq!(tree.children()[0].operator(), &Operator::Mul);
+}
+
+#[test]
+fn test_max_depth() {
+    let build = |expression: &str, max_depth| {
+        build_operator_tree_with_max_depth(expression, max_depth)
+            .map_err(|error| (error.position(), error.into_error()))
+    };
+    let max_depth_exceeded =
+        |position| Err((position, EvalexprError::MaxDepthExceeded { max_depth: 100 }));
+
+    // The position points to the token that nests too deeply
+    let nested_braces = "(".repeat(100_000) + "1" + &")".repeat(100_000);
+    assert_eq!(build(&nested_braces, 100), max_depth_exceeded(100));
+    let long_chain = "1".to_string() + &" + 1".repeat(100_000);
+    assert_eq!(build(&long_chain, 100), max_depth_exceeded(399_598));
+    let nested_negations = "-".repeat(100_000) + "1";
+    assert_eq!(build(&nested_negations, 100), max_depth_exceeded(100_000));
+
+    let tree = build_operator_tree_with_max_depth("(1 + 2) * 3", 4).unwrap();
+    assert_eq!(tree.depth(), 4);
+    assert_eq!(tree.eval(), Ok(Value::from(9)));
+    assert_eq!(
+        build("(1 + 2) * 3", 3),
+        Err((1, EvalexprError::MaxDepthExceeded { max_depth: 3 }))
+    );
+
+    // Deep trees can be built and dropped without a limit, as long as they are not evaluated
+    let tree = build_operator_tree(&long_chain).unwrap();
+    assert_eq!(tree.depth(), 100_001);
+    drop(tree);
+}
+
+#[test]
+fn test_eval_with_context_and_budget() {
+    let context = HashMapContext::new();
+    // The tree of `1 + 2` consists of the root node, the addition and the two constants
+    assert_eq!(
+        eval_with_context_and_budget("1 + 2", &context, 4),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        eval_with_context_and_budget("1 + 2", &context, 3),
+        Err(EvalexprError::BudgetExceeded { budget: 3 })
+    );
+
+    let large_expression = "1".to_string() + &" + 1".repeat(1000);
+    assert_eq!(
+        eval_with_context_and_budget(&large_expression, &context, 1000),
+        Err(EvalexprError::BudgetExceeded { budget: 1000 })
+    );
+    assert_eq!(
+        eval_with_context_and_budget(&large_expression, &context, 10_000),
+        Ok(Value::from(1001))
+    );
+
+    // Short-circuited operands are not counted
+    assert_eq!(
+        eval_with_context_and_budget("false && (1 + 2 + 3 + 4 == 10)", &context, 5),
+        Ok(Value::from(false))
+    );
+
+    // The budget also limits how often an expensive function can be called
+    let context = context_map! {
+        "expensive" => Function::new(|argument| Ok(argument.clone()))
+    }
+    .unwrap();
+    assert_eq!(
+        eval_with_context_and_budget("expensive(1) + expensive(2)", &context, 10),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        eval_with_context_and_budget(
+            "expensive(1) + expensive(2) + expensive(3) + expensive(4)",
+            &context,
+            10
+        ),
+        Err(EvalexprError::BudgetExceeded { budget: 10 })
+    );
+}
+
+#[test]
+fn test_custom_error_from_user_function() {
+    let context = context_map! {
+        "positive" => Function::new(|argument| {
+            if argument.as_int()? > 0 {
+                Ok(argument.clone())
+            } else {
+                Err(EvalexprError::custom("argument must be positive"))
+            }
+        }),
+        "domain_error" => Function::new(|argument| {
+            Err(EvalexprError::custom(format!("no value for {}", argument)))
+        })
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval_with_context("positive 3", &context),
+        Ok(Value::from(3))
+    );
+    let error = eval_with_context("1 + positive(-3)", &context).unwrap_err();
+    assert_eq!(
+        error,
+        EvalexprError::CustomMessage("argument must be positive".to_string())
+    );
+    assert_eq!(error.to_string(), "Error: argument must be positive");
+    assert_eq!(
+        eval_with_context("domain_error \"x\"", &context),
+        Err(EvalexprError::custom("no value for \"x\""))
+    );
+}
+
+#[test]
+fn test_assignments_return_value() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context_mut("x = 3", &mut context),
+        Ok(Value::Empty)
+    );
+    assert_eq!(
+        eval_with_context_mut("x += 3", &mut context),
+        Ok(Value::Empty)
+    );
+
+    context.set_assignments_return_value(true);
+    assert_eq!(
+        eval_with_context_mut("x = 3", &mut context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        eval_with_context_mut("y = (x = 3) + 1", &mut context),
+        Ok(Value::from(4))
+    );
+    assert_eq!(context.get_value("x"), Some(&Value::from(3)));
+    assert_eq!(context.get_value("y"), Some(&Value::from(4)));
+
+    // Chained assignment
+    assert_eq!(
+        eval_with_context_mut("a = b = 5", &mut context),
+        Ok(Value::from(5))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::from(5)));
+    assert_eq!(context.get_value("b"), Some(&Value::from(5)));
+    assert_eq!(
+        eval_with_context_mut("a += b *= 2", &mut context),
+        Ok(Value::from(15))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::from(15)));
+    assert_eq!(context.get_value("b"), Some(&Value::from(10)));
+
+    // Operator-assignments return the new value
+    assert_eq!(
+        eval_with_context_mut("x *= 2", &mut context),
+        Ok(Value::from(6))
+    );
+    assert_eq!(
+        eval_with_context_mut("s = \"a\"; s += \"b\"", &mut context),
+        Ok(Value::from("ab"))
+    );
+    assert_eq!(
+        eval_with_context_mut("flag = true; flag &&= false", &mut context),
+        Ok(Value::from(false))
+    );
+    // A failed assignment returns the error
+    assert_eq!(
+        eval_with_context_mut("x = 1.5", &mut context),
+        Err(EvalexprError::expected_int(Value::from(1.5)))
+    );
+
+    // Wrappers forward the setting
+    let mut chained = ChainedContext::new(context, HashMapContext::new());
+    assert_eq!(
+        eval_with_context_mut("z = 1", &mut chained),
+        Ok(Value::from(1))
+    );
+}
+
+#[test]
+fn test_chain_evaluates_to_last_expression() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context_mut("a = 1; b = 2; a + b", &mut context),
+        Ok(Value::from(3))
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::from(1)));
+    assert_eq!(context.get_value("b"), Some(&Value::from(2)));
+
+    // Later expressions see the assignments of earlier ones, also across evaluations
+    assert_eq!(
+        eval_with_context_mut("a = a + b; b = a * b; (a, b)", &mut context),
+        Ok(Value::from(vec![Value::from(3), Value::from(6)]))
+    );
+    assert_eq!(
+        eval_with_context_mut("a + b;", &mut context),
+        Ok(Value::Empty)
+    );
+
+    // An error in an expression stops the chain, but keeps earlier assignments
+    assert_eq!(
+        eval_with_context_mut("c = 1; a = true; c = 2", &mut context),
+        Err(EvalexprError::expected_int(Value::from(true)))
+    );
+    assert_eq!(context.get_value("c"), Some(&Value::from(1)));
+}
+
+#[test]
+fn test_index_operator() {
+    let context = context_map! {
+        "s" => "héllo",
+        "t" => Value::from(vec![
+            Value::from(1),
+            Value::from(vec![Value::from(2), Value::from(3)]),
+        ]),
+        "id" => Function::new(|argument| Ok(argument.clone()))
+    }
+    .unwrap();
+
+    // Strings are indexed by characters
+    assert_eq!(eval_with_context("s[0]", &context), Ok(Value::from("h")));
+    assert_eq!(eval_with_context("s[1]", &context), Ok(Value::from("é")));
+    assert_eq!(eval("\"abc\"[2]"), Ok(Value::from("c")));
+
+    assert_eq!(eval_with_context("t[0]", &context), Ok(Value::from(1)));
+    assert_eq!(eval_with_context("t[1][0]", &context), Ok(Value::from(2)));
+    assert_eq!(
+        eval_with_context("t[0 + 1][1] * 2", &context),
+        Ok(Value::from(6))
+    );
+    assert_eq!(eval_with_context("-t[0]", &context), Ok(Value::from(-1)));
+    assert_eq!(eval_with_context("id(t)[0]", &context), Ok(Value::from(1)));
+    assert_eq!(eval("(1, 2, 3)[2]"), Ok(Value::from(3)));
+    assert_eq!(
+        eval_with_context("get(t, 1)[1] == t[1][1]", &context),
+        Ok(Value::from(true))
+    );
+
+    // Out-of-range and invalid indices
+    assert_eq!(
+        eval_with_context("t[2]", &context),
+        Err(EvalexprError::CustomMessage(
+            "[] index 2 is out of bounds for a tuple of length 2".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("s[5]", &context),
+        Err(EvalexprError::CustomMessage(
+            "[] index 5 is out of bounds for a string of 5 characters".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("s[-1]", &context),
+        Err(EvalexprError::CustomMessage(
+            "[] expected a non-negative integer, but got -1".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("t[1.0]", &context),
+        Err(EvalexprError::expected_int(Value::from(1.0)))
+    );
+    assert_eq!(
+        eval("1[0]"),
+        Err(EvalexprError::type_error(
+            Value::from(1),
+            vec![ValueType::String, ValueType::Tuple]
+        ))
+    );
+
+    // Unmatched brackets
+    assert_eq!(
+        eval_with_context("t[1", &context),
+        Err(EvalexprError::UnmatchedLBracket)
+    );
+    assert_eq!(
+        eval_with_context("t[1]]", &context),
+        Err(EvalexprError::UnmatchedRBracket)
+    );
+    assert_eq!(
+        eval_with_context("t[(1])", &context),
+        Err(EvalexprError::UnmatchedRBracket)
+    );
+    assert_eq!(
+        eval_with_context("(t[1)]", &context),
+        Err(EvalexprError::UnmatchedRBrace)
+    );
+
+    // Operator trees with indexing are displayed with brackets
+    for expression in ["t[1][0]", "(a + b)[c]", "f x[0]", "t[i, j]"].iter() {
+        assert_eq!(
+            build_operator_tree(expression).unwrap().to_string(),
+            *expression
+        );
+    }
+}
+
+#[test]
+fn test_tuple_trailing_comma() {
+    assert_eq!(eval("(1,)"), Ok(Value::from(vec![Value::from(1)])));
+    assert_eq!(eval("(1)"), Ok(Value::from(1)));
+    assert_eq!(
+        eval("(1, 2,)"),
+        Ok(Value::from(vec![Value::from(1), Value::from(2)]))
+    );
+    assert_eq!(
+        eval("1, 2,"),
+        Ok(Value::from(vec![Value::from(1), Value::from(2)]))
+    );
+    assert_eq!(
+        eval("((1,), (2, 3,),)"),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::from(1)]),
+            Value::from(vec![Value::from(2), Value::from(3)])
+        ]))
+    );
+    assert_eq!(eval("len((\"a\",))"), Ok(Value::from(1)));
+    assert_eq!(eval("(7,)[0]"), Ok(Value::from(7)));
+
+    // Only a single trailing comma after a non-empty element is ignored
+    assert_eq!(
+        eval("(,)"),
+        Ok(Value::from(vec![Value::Empty, Value::Empty]))
+    );
+    assert_eq!(
+        eval("(1,,)"),
+        Ok(Value::from(vec![
+            Value::from(1),
+            Value::Empty,
+            Value::Empty
+        ]))
+    );
+
+    // Single-element tuples are displayed with a trailing comma
+    assert_eq!(build_operator_tree("(1,)").unwrap().to_string(), "(1,)");
+    assert_eq!(
+        build_operator_tree("(1, 2,)").unwrap().to_string(),
+        "(1, 2)"
+    );
+    let single = Value::Tuple(vec![Value::Int(1)]);
+    assert_eq!(single.to_string(), "(1,)");
+    assert_eq!(eval(&single.to_string()), Ok(single.clone()));
+    let folded = build_operator_tree("(1,)").unwrap().fold_constants();
+    assert_eq!(eval(&folded.to_string()), Ok(single));
+}
+
+#[test]
+fn test_builtin_map() {
+    assert_eq!(
+        eval("map((1.0, 4.0, 9.0), \"math::sqrt\")"),
+        Ok(Value::from(vec![
+            Value::from(1.0),
+            Value::from(2.0),
+            Value::from(3.0)
+        ]))
+    );
+    assert_eq!(
+        eval("map((4.0,), \"math::sqrt\")"),
+        Ok(Value::from(vec![Value::from(2.0)]))
+    );
+
+    let context = context_map! {
+        "double" => Function::new(|argument| Ok(Value::from(argument.as_int()? * 2))),
+        // Context functions take precedence over builtin functions
+        "len" => Function::new(|_| Ok(Value::from(0)))
+    }
+    .unwrap();
+    assert_eq!(
+        eval_with_context("map((1, 2, 3), \"double\")", &context),
+        Ok(Value::from(vec![
+            Value::from(2),
+            Value::from(4),
+            Value::from(6)
+        ]))
+    );
+    assert_eq!(
+        eval_with_context("map((\"a\", \"bc\"), \"len\")", &context),
+        Ok(Value::from(vec![Value::from(0), Value::from(0)]))
+    );
+    assert_eq!(
+        eval("map((\"a\", \"bc\"), \"len\")"),
+        Ok(Value::from(vec![Value::from(1), Value::from(2)]))
+    );
+
+    assert_eq!(
+        eval("map((1, 2), \"unknown\")"),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "unknown".to_string()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("map((1, \"x\"), \"double\")", &context),
+        Err(EvalexprError::expected_int(Value::from("x")))
+    );
+    assert_eq!(
+        eval("map(1, \"math::sqrt\")"),
+        Err(EvalexprError::expected_tuple(Value::from(1)))
+    );
+}
+
+#[test]
+fn test_builtin_filter() {
+    let context = context_map! {
+        "is_positive" => Function::new(|argument| Ok(Value::from(argument.as_number()? > 0.0))),
+        "identity" => Function::new(|argument| Ok(argument.clone()))
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval_with_context("filter((3, -1, 0, 2.5, -7.0), \"is_positive\")", &context),
+        Ok(Value::from(vec![Value::from(3), Value::from(2.5)]))
+    );
+    assert_eq!(
+        eval_with_context("filter((-1, -2), \"is_positive\")", &context),
+        Ok(Value::from(vec![]))
+    );
+    // Builtin predicates work as well
+    assert_eq!(
+        eval("filter((1, \"a\", 2.0, true), \"is_number\")"),
+        Ok(Value::from(vec![Value::from(1), Value::from(2.0)]))
+    );
+
+    // The predicate must return a boolean
+    assert_eq!(
+        eval_with_context("filter((true, 1), \"identity\")", &context),
+        Err(EvalexprError::expected_boolean(Value::from(1)))
+    );
+    assert_eq!(
+        eval_with_context("filter((1, 2), \"unknown\")", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "unknown".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_builtin_reduce() {
+    let context = context_map! {
+        "add" => Function::new_fixed(2, |arguments| {
+            Ok(Value::from(arguments[0].as_int()? + arguments[1].as_int()?))
+        }),
+        "append" => Function::new_fixed(2, |arguments| {
+            Ok(Value::from(arguments[0].as_string()? + &arguments[1].as_string()?))
+        })
+    }
+    .unwrap();
+
+    assert_eq!(
+        eval_with_context("reduce((1, 2, 3, 4), \"add\", 0)", &context),
+        Ok(Value::from(10))
+    );
+    assert_eq!(
+        eval_with_context("reduce((\"b\", \"c\"), \"append\", \"a\")", &context),
+        Ok(Value::from("abc"))
+    );
+    // Builtin functions work as well, and the tuple is folded from the left
+    assert_eq!(
+        eval("reduce((1, 2, 3, 4), \"math::product\", 1)"),
+        Ok(Value::from(24))
+    );
+    assert_eq!(eval("reduce((2, 3), \"max\", 5)"), Ok(Value::from(5)));
+    assert_eq!(
+        eval("reduce((1,), \"str::from\", \"x\")"),
+        Ok(Value::from("(\"x\", 1)"))
+    );
+
+    assert_eq!(
+        eval_with_context("reduce((1, \"x\"), \"add\", 0)", &context),
+        Err(EvalexprError::expected_int(Value::from("x")))
+    );
+    assert_eq!(
+        eval("reduce((1, 2), \"unknown\", 0)"),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "unknown".to_string()
+        ))
+    );
+    assert_eq!(
+        eval("reduce((1, 2), \"add\")"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 3,
+            actual: Value::from(vec![
+                Value::from(vec![Value::from(1), Value::from(2)]),
+                Value::from("add")
+            ])
+        })
+    );
+}
+
+#[test]
+fn test_bitwise_operators() {
+    assert_eq!(eval("6 & 3"), Ok(Value::from(2)));
+    assert_eq!(eval("6 | 1"), Ok(Value::from(7)));
+    assert_eq!(eval("-1 & 255"), Ok(Value::from(255)));
+
+    // Bitwise operators bind tighter than comparisons, but weaker than arithmetic
+    assert_eq!(eval("6 & 3 == 2"), Ok(Value::from(true)));
+    assert_eq!(eval("1 + 2 & 3"), Ok(Value::from(3)));
+    assert_eq!(eval("1 | 2 & 4"), Ok(Value::from(1)));
+
+    // Doubled characters are still the logical operators
+    assert_eq!(eval("true && false || true"), Ok(Value::from(true)));
+    assert_eq!(eval("a = true; a &&= false; a"), Ok(Value::from(false)));
+
+    assert_eq!(
+        eval("1.0 & 1"),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::from(1.0)
+        })
+    );
+    assert_eq!(
+        eval("true | 1"),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::from(true)
+        })
+    );
+
+    assert_eq!(
+        build_operator_tree("a & b | c").unwrap().to_string(),
+        "a & b | c"
+    );
+    assert_eq!(
+        build_operator_tree("a & (b | c)").unwrap().to_string(),
+        "a & (b | c)"
+    );
+}
@@ -16,8 +16,80 @@ fn test_random() {
     }
 }
 
+#[test]
+fn test_random_range() {
+    for _ in 0..100 {
+        assert_expr("-2.5 <= random(-2.5, 4)");
+        assert_expr("random(-2.5, 4) <= 4");
+        assert_expr("random(1, 1) == 1.0");
+        assert_expr("math::is_finite(random(-1.7976931348623157e308, 1.7976931348623157e308))");
+    }
+    // This has a probability of 1e-20 of failing
+    assert_expr("random(10, 20) != random(10, 20)");
+}
+
+#[test]
+fn test_random_is_not_folded() {
+    let tree = build_operator_tree("random() + 2 * 3")
+        .unwrap()
+        .fold_constants();
+    assert_eq!(tree.to_string(), "random() + 6");
+    for _ in 0..100 {
+        // This has a probability of 1e-20 of failing
+        assert_ne!(tree.eval(), tree.eval());
+    }
+}
+
+#[test]
+fn test_random_int() {
+    for _ in 0..100 {
+        assert_expr("-3 <= random_int(-3, 3)");
+        assert_expr("random_int(-3, 3) <= 3");
+        assert_expr("random_int(5, 5) == 5");
+    }
+    // This has a probability of 1e-9 of failing
+    assert_expr("random_int(0, 1000000000) != random_int(0, 1000000000)");
+}
+
 #[test]
 fn test_random_errors() {
     assert!(eval("random(9)").is_err());
     assert!(eval("random(\"a\", \"b\")").is_err());
+    assert!(eval("random(1, 2, 3)").is_err());
+    assert_eq!(
+        eval("random(2, 1)"),
+        Err(EvalexprError::CustomMessage(
+            "random expected finite min <= max, but got min = 2 and max = 1".to_string()
+        ))
+    );
+    assert!(eval("random_int(3)").is_err());
+    assert!(eval("random_int(1.0, 2.0)").is_err());
+    assert_eq!(
+        eval("random_int(3, 2)"),
+        Err(EvalexprError::CustomMessage(
+            "random_int expected min <= max, but got min = 3 and max = 2".to_string()
+        ))
+    );
+}
+
+fn random_sequence(seed: u64) -> Vec<Value> {
+    let mut context = HashMapContext::new();
+    context.set_random_seed(seed);
+    (0..10)
+        .map(|_| eval_with_context("(random(), random_int(0, 1000))", &context).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_random_seed() {
+    assert_eq!(random_sequence(42), random_sequence(42));
+    assert_ne!(random_sequence(42), random_sequence(43));
+
+    let mut context = HashMapContext::new();
+    context.set_random_seed(0);
+    assert_eq!(
+        eval_with_context("0 <= random() && random() <= 1", &context),
+        Ok(Value::Boolean(true))
+    );
+    assert!(eval_with_context("random(9)", &context).is_err());
 }
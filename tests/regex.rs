@@ -29,3 +29,78 @@ fn test_regex_functions() {
         Ok(Value::String("foobar".to_owned()))
     );
 }
+
+#[test]
+fn test_regex_captures() {
+    assert_eq!(
+        eval("str::regex_captures(\"Released on 2022-05-13.\", \"(\\\\d{4})-(\\\\d{2})-(\\\\d{2})\")"),
+        Ok(Value::Tuple(vec![
+            Value::from("2022-05-13"),
+            Value::from("2022"),
+            Value::from("05"),
+            Value::from("13")
+        ]))
+    );
+    assert_eq!(
+        eval("str::regex_captures(\"2022-05\", \"(\\\\d{4})-(\\\\d{2})(-(\\\\d{2}))?\")"),
+        Ok(Value::Tuple(vec![
+            Value::from("2022-05"),
+            Value::from("2022"),
+            Value::from("05"),
+            Value::Empty,
+            Value::Empty
+        ]))
+    );
+    assert_eq!(
+        eval("str::regex_captures(\"no date here\", \"(\\\\d{4})-(\\\\d{2})-(\\\\d{2})\")"),
+        Ok(Value::Tuple(vec![]))
+    );
+    match eval("str::regex_captures(\"foo\", \"[\")") {
+        Err(EvalexprError::InvalidRegex { regex, message }) => {
+            assert_eq!(regex, "[");
+            assert!(message.contains("unclosed character class"));
+        },
+        v => panic!("{:?}", v),
+    };
+}
+
+#[test]
+fn test_regex_split() {
+    assert_eq!(
+        eval("str::regex_split(\"a b  c\\t\\nd\", \"\\\\s+\")"),
+        Ok(Value::Tuple(vec![
+            Value::from("a"),
+            Value::from("b"),
+            Value::from("c"),
+            Value::from("d")
+        ]))
+    );
+    // Each match of a single whitespace is a separate delimiter
+    assert_eq!(
+        eval("str::regex_split(\"a  b\", \"\\\\s\")"),
+        Ok(Value::Tuple(vec![
+            Value::from("a"),
+            Value::from(""),
+            Value::from("b")
+        ]))
+    );
+    assert_eq!(
+        eval("str::regex_split(\" a \", \"\\\\s+\")"),
+        Ok(Value::Tuple(vec![
+            Value::from(""),
+            Value::from("a"),
+            Value::from("")
+        ]))
+    );
+    assert_eq!(
+        eval("str::regex_split(\"abc\", \",\")"),
+        Ok(Value::Tuple(vec![Value::from("abc")]))
+    );
+    match eval("str::regex_split(\"foo\", \"[\")") {
+        Err(EvalexprError::InvalidRegex { regex, message }) => {
+            assert_eq!(regex, "[");
+            assert!(message.contains("unclosed character class"));
+        },
+        v => panic!("{:?}", v),
+    };
+}
@@ -14,6 +14,23 @@ fn test_serde() {
     }
 }
 
+#[test]
+fn test_serde_round_trip() {
+    let strings = [
+        "3",
+        "4 + 4",
+        "21 ^ (2 * 2) - -3 > 5 || !true",
+        "(a, \"b\"); f(a)",
+    ];
+
+    for string in &strings {
+        let tree = build_operator_tree(string).unwrap();
+        let serialized = ron::ser::to_string(&tree).unwrap();
+        assert_eq!(serialized, format!("{:?}", string));
+        assert_eq!(ron::de::from_str::<Node>(&serialized), Ok(tree));
+    }
+}
+
 #[test]
 fn test_serde_errors() {
     assert_eq!(
@@ -24,11 +41,10 @@ fn test_serde_errors() {
         })
     );
     assert_eq!(
-        ron::de::from_str::<Node>("\"&\""),
+        ron::de::from_str::<Node>("\"(\""),
         Err(ron::Error {
             code: ron::de::ErrorCode::Message(
-                "Found a partial token '&' that should be followed by another partial token."
-                    .to_owned()
+                "Found an unmatched opening parenthesis '('.".to_owned()
             ),
             position: ron::de::Position { line: 0, col: 0 }
         })